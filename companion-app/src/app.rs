@@ -2,10 +2,6 @@
 // is instantiated by main.rs
 // TODO:
 // - refactor viewport structs out into multiple files and use cfg attributes in mod file
-// - add resources in-file scroll target links (e.g. cache.add_link_hook("#next");
-//      - if self.cache.get_link_hook("#next").unwrap() {
-//            self.curr_page = 1;
-//       })
 
 use std::{rc::Rc, sync::Arc};
 
@@ -19,10 +15,14 @@ use crate::{
         feature_state::FeatureSubsystem,
         language_helper::language_helper_feature::LanguageHelperSubsystem,
         locales::LocaleSubsystem,
+        network::NetworkAccess,
         notes_feature::NotesSubsystem,
+        resource_editor::ResourceEditor,
+        resource_packs::ResourcePackSubsystem,
         ressources_feature::RessourcesSubsystem,
         settings::SettingsSubsystem,
         storage::{FileStorage, PersistentStorage, SaveState},
+        ui_strings,
     },
     frontend::{
         self,
@@ -46,12 +46,18 @@ pub struct OverlayApp {
 
     pub ressources: RessourcesSubsystem,
 
+    pub resource_editor: ResourceEditor,
+
+    pub resource_packs: ResourcePackSubsystem,
+
     pub notes: NotesSubsystem,
 
     pub language_helper: LanguageHelperSubsystem,
 
     pub locales: Rc<LocaleSubsystem>,
 
+    pub network: NetworkAccess, // every download goes through it
+
     #[allow(dead_code)]
     pub async_manager: Rc<AsyncManager>,
 
@@ -67,18 +73,26 @@ impl OverlayApp {
 
         // tokio async manager only ever needs immutable access to schedule new tasks
         let async_manager = Rc::new(AsyncManager::new());
+        // configured from the settings, so nothing may download before they are loaded
+        let network = NetworkAccess::new(Default::default());
         // locale subsystem is mainly read-only, except the occasional re-initialization and locale
         // update for which it uses interior mutability -> it can be passed around in an Rc
-        let locale_subsystem = Rc::new(LocaleSubsystem::new(async_manager.clone()));
+        let locale_subsystem = Rc::new(LocaleSubsystem::new(
+            async_manager.clone(),
+            network.clone(),
+        ));
 
         let mut app = Self {
             features: FeatureSubsystem::new(),
             gui: GuiSubsystem::new(cc),
             settings: SettingsSubsystem::new(),
-            ressources: RessourcesSubsystem::new(),
+            ressources: RessourcesSubsystem::new(locale_subsystem.clone()),
+            resource_editor: ResourceEditor::new(),
+            resource_packs: ResourcePackSubsystem::new(async_manager.clone(), network.clone()),
             notes: NotesSubsystem::new(),
             language_helper: LanguageHelperSubsystem::new(locale_subsystem.clone()),
             locales: locale_subsystem,
+            network,
             async_manager: async_manager.clone(),
             storage: Box::new(FileStorage::new()),
             viewport_manager: Box::new(DefaultViewportManager::default()),
@@ -88,6 +102,13 @@ impl OverlayApp {
         // setup storage and load settings
         app.setup_persistent_storage();
 
+        // locales may have to be downloaded, which depends on the loaded network policy
+        app.apply_network_settings();
+        app.locales.trigger_initialization();
+
+        // before the viewport manager, which might have to explain itself to the user
+        ui_strings::set_ui_locale(&app.settings.ui_locale, 0);
+
         app.setup_native_viewport_manager();
 
         app
@@ -172,6 +193,11 @@ impl OverlayApp {
         }
     }
 
+    fn apply_network_settings(&self) {
+        self.network
+            .configure(self.settings.network_mode, &self.settings.mirror_url);
+    }
+
     // replace values in overlay app with loaded save_state
     pub fn push_save_state_into_app(&mut self, save_state: SaveState) {
         // rerouted for editing convenience of SaveState properties
@@ -242,8 +268,22 @@ impl App for OverlayApp {
             })
         }
 
+        self.apply_network_settings();
+        ui_strings::set_ui_locale(
+            &self.settings.ui_locale,
+            *self.locales.init_counter.read().unwrap(),
+        );
+        self.language_helper
+            .set_clipboard_watching(self.settings.clipboard_translation);
         self.language_helper.update_subsystem();
 
+        // Alt+Y copies the top translation, the native viewport managers register it globally,
+        // so it also works from inside the game
+        let copy_shortcut = ctx.input_mut(|i| i.consume_key(Modifiers::ALT, egui::Key::Y));
+        if self.viewport_manager.take_copy_translation_request() || copy_shortcut {
+            self.language_helper.copy_top_translation();
+        }
+
         // only handle input when control_bar is also visible
         // and the application is currently meant to be controlled
         if self.viewport_manager.current_focus_state().is_focused() {
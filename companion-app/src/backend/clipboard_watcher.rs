@@ -0,0 +1,194 @@
+// watches the system clipboard for copied chat text (e.g. a foreign item name) and hands it to the
+// Language Helper. the system clipboard is only touched by the polling thread, everything that
+// decides what to do with the text is plain rust and works without a display
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+const CLIPBOARD_POLL_INTERVAL: Duration = Duration::from_millis(400);
+// longer texts are whole chat messages or something else entirely, not a name to look up
+const MAX_PROMPT_CHARS: usize = 40;
+
+/// where the clipboard text comes from, the system clipboard or a fake one in tests
+pub trait ClipboardSource {
+    fn read_text(&mut self) -> Option<String>;
+}
+
+impl ClipboardSource for arboard::Clipboard {
+    fn read_text(&mut self) -> Option<String> {
+        self.get_text().ok()
+    }
+}
+
+/// tells apart new clipboard contents from the ones already seen or copied by the app itself
+#[derive(Default)]
+pub struct ClipboardChangeDetector {
+    primed: bool, // whatever was in the clipboard before watching started is ignored
+    last_text: Option<String>,
+    own_copy: Option<String>,
+}
+
+impl ClipboardChangeDetector {
+    /// the new clipboard text, if it changed since the last check
+    pub fn check(&mut self, text: Option<String>) -> Option<String> {
+        if !self.primed {
+            self.primed = true;
+            self.last_text = text;
+            return None;
+        }
+        if text.is_none() || text == self.last_text {
+            return None;
+        }
+
+        self.last_text = text.clone();
+        match self.own_copy.take() {
+            Some(own) if text.as_ref() == Some(&own) => None,
+            _ => text,
+        }
+    }
+
+    /// the next change to `text` comes from the app, e.g. a copied translation
+    pub fn ignore_next(&mut self, text: impl Into<String>) {
+        self.own_copy = Some(text.into());
+    }
+}
+
+/// turns copied text into a search prompt, None if it doesn't look like a name
+pub fn clipboard_search_prompt(text: &str) -> Option<String> {
+    let prompt = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let too_long = prompt.chars().count() > MAX_PROMPT_CHARS;
+    let is_link = prompt.contains("://");
+    match too_long || is_link {
+        true => None,
+        false => Some(prompt),
+    }
+}
+
+/// polls the clipboard on its own thread (reading it can block for a moment on X11)
+/// and stops when dropped
+pub struct ClipboardWatcher {
+    changes: Receiver<String>,
+    own_copies: Sender<String>,
+    stop: Arc<AtomicBool>,
+}
+
+impl ClipboardWatcher {
+    pub fn start() -> Option<Self> {
+        // on wayland this uses the data-control protocol if the compositor has it, else XWayland
+        match arboard::Clipboard::new() {
+            Ok(clipboard) => Some(Self::start_with(clipboard)),
+            Err(e) => {
+                eprintln!("ClipboardWatcher - couldn't access the clipboard: {e}");
+                None
+            }
+        }
+    }
+
+    pub fn start_with(mut source: impl ClipboardSource + Send + 'static) -> Self {
+        let (changes_tx, changes) = mpsc::channel();
+        let (own_copies, own_copies_rx) = mpsc::channel::<String>();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let stop_flag = stop.clone();
+        thread::spawn(move || {
+            let mut detector = ClipboardChangeDetector::default();
+            while !stop_flag.load(Ordering::Relaxed) {
+                let text = source.read_text();
+                // own copies are announced before they happen, so after reading is early enough
+                if let Some(own_copy) = own_copies_rx.try_iter().last() {
+                    detector.ignore_next(own_copy);
+                }
+                if let Some(text) = detector.check(text)
+                    && changes_tx.send(text).is_err()
+                {
+                    break; // watcher is gone
+                }
+                thread::sleep(CLIPBOARD_POLL_INTERVAL);
+            }
+            println!("ClipboardWatcher - stopped watching the clipboard");
+        });
+
+        println!("ClipboardWatcher - started watching the clipboard");
+        Self {
+            changes,
+            own_copies,
+            stop,
+        }
+    }
+
+    /// the latest search prompt copied since the last call, if any
+    pub fn take_search_prompt(&self) -> Option<String> {
+        self.changes
+            .try_iter()
+            .last()
+            .and_then(|text| clipboard_search_prompt(&text))
+    }
+
+    /// call before putting `text` into the clipboard, so it isn't searched for again
+    pub fn ignore_own_copy(&self, text: impl Into<String>) {
+        let _ = self.own_copies.send(text.into());
+    }
+}
+
+impl Drop for ClipboardWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_change_detector() {
+        let mut detector = ClipboardChangeDetector::default();
+        // already in the clipboard when watching started
+        assert_eq!(detector.check(Some("old".to_owned())), None);
+        assert_eq!(
+            detector.check(Some("Glurak".to_owned())),
+            Some("Glurak".to_owned())
+        );
+        assert_eq!(detector.check(Some("Glurak".to_owned())), None);
+        assert_eq!(detector.check(None), None);
+
+        detector.ignore_next("Charizard");
+        assert_eq!(detector.check(Some("Charizard".to_owned())), None);
+        assert_eq!(
+            detector.check(Some("Dracaufeu".to_owned())),
+            Some("Dracaufeu".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_clipboard_search_prompt() {
+        assert_eq!(
+            clipboard_search_prompt("  Glurak \n second line"),
+            Some("Glurak".to_owned())
+        );
+        assert_eq!(
+            clipboard_search_prompt("Hyper   Potion"),
+            Some("Hyper Potion".to_owned())
+        );
+        assert_eq!(clipboard_search_prompt(" \n "), None);
+        assert_eq!(clipboard_search_prompt("https://pokemmo.com"), None);
+        assert_eq!(
+            clipboard_search_prompt("WTS shiny Glurak, 5x31 adamant, pm me offers in global chat"),
+            None
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// loads the language independent base data (assets/data) that data-bound resources are built from
+// all names in here are the lowercase english names, which are also the keys into every locale
+
+use serde::{Deserialize, de::DeserializeOwned};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::utils::find_asset_folder;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MonsterData {
+    pub id: u32,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemData {
+    pub id: u32,
+    pub name: String,
+    pub desc: String,
+    pub icon_id: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MoveData {
+    pub id: u32,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub move_type: String,
+    pub skill_damage_type: String,
+    pub base_power: u32,
+    pub base_accuracy: u32,
+    pub base_pp: u32,
+    pub priority: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EncounterData {
+    #[serde(rename = "type")]
+    pub encounter_type: String,
+    pub pokemon_id: u32,
+    pub min_level: u32,
+    pub max_level: u32,
+    pub time_restrictions: Vec<String>,
+    pub rarity: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LocationData {
+    pub name: String,
+    pub region_base_name: String,
+    pub encounters: Vec<EncounterData>,
+}
+
+/// everything from assets/data, indexed by lowercase english name
+#[derive(Debug, Default)]
+pub struct GameData {
+    monsters: HashMap<String, MonsterData>,
+    monster_names_by_id: HashMap<u32, String>,
+    items: HashMap<String, ItemData>,
+    moves: HashMap<String, MoveData>,
+    // the same location name exists in multiple regions (e.g. "route 1")
+    locations: HashMap<String, Vec<LocationData>>,
+
+    sprite_folder: Option<PathBuf>,
+}
+
+impl GameData {
+    /// never fails, missing or broken files just leave their part of the data empty
+    pub fn load() -> Self {
+        let mut game_data = GameData::default();
+
+        let asset_folder = match find_asset_folder() {
+            Ok(folder) => folder,
+            Err(e) => {
+                eprintln!("GameData - no asset folder, data cards will stay empty: {e}");
+                return game_data;
+            }
+        };
+        let data_folder = asset_folder.join("data");
+
+        match parse_json_file::<Vec<MonsterData>>(data_folder.join("monsters.json")) {
+            Ok(monsters) => {
+                for monster in monsters {
+                    game_data
+                        .monster_names_by_id
+                        .insert(monster.id, monster.name.clone());
+                    game_data.monsters.insert(monster.name.clone(), monster);
+                }
+            }
+            Err(e) => eprintln!("GameData - couldn't load monsters.json: {e}"),
+        }
+
+        match parse_json_file::<Vec<ItemData>>(data_folder.join("items.json")) {
+            Ok(items) => {
+                for item in items {
+                    // a few key items exist multiple times, the first one is good enough
+                    game_data.items.entry(item.name.clone()).or_insert(item);
+                }
+            }
+            Err(e) => eprintln!("GameData - couldn't load items.json: {e}"),
+        }
+
+        match parse_json_file::<Vec<MoveData>>(data_folder.join("skills.json")) {
+            Ok(moves) => {
+                for move_data in moves {
+                    game_data.moves.insert(move_data.name.clone(), move_data);
+                }
+            }
+            Err(e) => eprintln!("GameData - couldn't load skills.json: {e}"),
+        }
+
+        match parse_json_file::<Vec<LocationData>>(data_folder.join("locations.json")) {
+            Ok(locations) => {
+                for location in locations {
+                    game_data
+                        .locations
+                        .entry(location.name.clone())
+                        .or_default()
+                        .push(location);
+                }
+            }
+            Err(e) => eprintln!("GameData - couldn't load locations.json: {e}"),
+        }
+
+        let sprite_folder = asset_folder.join("sprites");
+        if sprite_folder.is_dir() {
+            game_data.sprite_folder = Some(sprite_folder);
+        }
+
+        println!(
+            "GameData - loaded {} monsters, {} items, {} moves, {} locations",
+            game_data.monsters.len(),
+            game_data.items.len(),
+            game_data.moves.len(),
+            game_data.locations.len()
+        );
+
+        game_data
+    }
+
+    pub fn get_monster(&self, name: &str) -> Option<&MonsterData> {
+        self.monsters.get(&data_key(name))
+    }
+
+    pub fn get_monster_name(&self, id: u32) -> Option<&String> {
+        self.monster_names_by_id.get(&id)
+    }
+
+    pub fn get_item(&self, name: &str) -> Option<&ItemData> {
+        self.items.get(&data_key(name))
+    }
+
+    pub fn get_move(&self, name: &str) -> Option<&MoveData> {
+        self.moves.get(&data_key(name))
+    }
+
+    pub fn get_locations(&self, name: &str) -> &[LocationData] {
+        self.locations
+            .get(&data_key(name))
+            .map(|l| l.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// file:// uri of an optional sprite (assets/sprites/<kind>/<id>.png), if the user has one
+    pub fn get_sprite_uri(&self, kind: &str, id: u32) -> Option<String> {
+        let sprite = self
+            .sprite_folder
+            .as_ref()?
+            .join(kind)
+            .join(format!("{id}.png"));
+        sprite
+            .is_file()
+            .then(|| format!("file://{}", sprite.to_string_lossy()))
+    }
+}
+
+/// turns whatever a guide author wrote ("Ho-Oh", " Viridian Forest ") into a data key
+pub fn data_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+fn parse_json_file<T>(path: impl AsRef<Path>) -> io::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let content = fs::read_to_string(path)?;
+    let value = serde_json::from_str(&content)?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_monsters() {
+        let data_folder = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/data");
+        let monsters: Vec<MonsterData> =
+            parse_json_file(data_folder.join("monsters.json")).unwrap();
+        assert_eq!(monsters.len(), 649);
+
+        let garchomp = monsters.iter().find(|m| m.name == "garchomp").unwrap();
+        assert_eq!(garchomp.id, 445);
+        assert_eq!(garchomp.types, ["dragon", "ground"]);
+        assert!(monsters.iter().all(|m| !m.types.is_empty()));
+    }
+}
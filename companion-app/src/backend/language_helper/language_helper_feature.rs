@@ -1,11 +1,24 @@
 use super::super::locales::TextCategory;
 use super::super::search::search_index::SearchIndex;
-use crate::backend::language_helper::locale_data_bridge::LocaleDataBridge;
-use crate::backend::locales::LocaleSubsystem;
+use crate::backend::clipboard_watcher::ClipboardWatcher;
+use crate::backend::language_helper::locale_data_bridge::{ANY_LOCALE, LocaleDataBridge};
+use crate::backend::language_helper::phrase_translator::{PhraseDictionary, PhraseSegment};
+use crate::backend::locales::{Locale, LocaleSubsystem};
 use crate::backend::search::data_bridge::DataBridge;
 use crate::backend::search::search_update::UpdateRequest;
+use std::cell::RefCell;
 use std::rc::Rc;
 
+/// a search result next to its translation
+pub struct TranslationPair {
+    pub category: TextCategory,
+    pub key: String,
+    pub source_text: String,
+    pub translation: String,
+    pub source_locale: Option<String>, // only shown in "any language" mode
+    pub overridden: bool,              // the translation is a user fix
+}
+
 pub struct LanguageHelperSubsystem {
     pub locale_subsystem: Rc<LocaleSubsystem>,
     locale_init_counter: usize, // used to compare against locale_subs counter -> update?
@@ -13,6 +26,15 @@ pub struct LanguageHelperSubsystem {
     // might or might not be valid locale keys at any given time
     from_locale: String,
     to_locale: String,
+    compare_all_locales: bool, // every hit lists its text in all locales
+    // only running while enabled in the settings
+    clipboard_watcher: Option<ClipboardWatcher>,
+    clipboard_unavailable: bool, // don't retry every frame
+    // own handle for copying, so copies also work while another window has the focus
+    clipboard: Option<arboard::Clipboard>,
+    // whole chat lines, the dictionary is rebuilt when the source locale or locale data changes
+    phrase: String,
+    phrase_dictionary: RefCell<Option<((String, usize), PhraseDictionary)>>,
 }
 
 impl LanguageHelperSubsystem {
@@ -25,10 +47,26 @@ impl LanguageHelperSubsystem {
             search_index: SearchIndex::new(search_data_bridge),
             from_locale: String::new(),
             to_locale: String::new(),
+            compare_all_locales: false,
+            clipboard_watcher: None,
+            clipboard_unavailable: false,
+            clipboard: None,
+            phrase: String::new(),
+            phrase_dictionary: RefCell::new(None),
         }
     }
 
     pub fn update_subsystem(&mut self) {
+        // copied text is searched right away
+        if let Some(prompt) = self
+            .clipboard_watcher
+            .as_ref()
+            .and_then(|watcher| watcher.take_search_prompt())
+        {
+            println!("LanguageHelper - searching copied text \"{prompt}\"");
+            self.set_search_prompt(prompt);
+        }
+
         // check if locale subsystem was re-initialized
         let counter_guard = self.locale_subsystem.init_counter.read().unwrap();
         if self.locale_init_counter < *counter_guard {
@@ -44,6 +82,68 @@ impl LanguageHelperSubsystem {
         self.search_index.update_search_index();
     }
 
+    /// starts/stops the clipboard watcher, called every frame with the setting
+    pub fn set_clipboard_watching(&mut self, enabled: bool) {
+        match (enabled, &self.clipboard_watcher) {
+            (true, None) if !self.clipboard_unavailable => {
+                self.clipboard_watcher = ClipboardWatcher::start();
+                self.clipboard_unavailable = self.clipboard_watcher.is_none();
+            }
+            (false, Some(_)) => self.clipboard_watcher = None,
+            (false, None) => self.clipboard_unavailable = false,
+            _ => (),
+        }
+    }
+
+    pub fn is_watching_clipboard(&self) -> bool {
+        self.clipboard_watcher.is_some()
+    }
+
+    /// the translation of the best search result
+    pub fn get_top_translation(&self) -> Option<String> {
+        let top_match = *self.search_index.matches.first()?;
+        let hit = self
+            .search_index
+            .data_bridge
+            .get_items(&[top_match])
+            .pop()?;
+        self.locale_subsystem
+            .with_locale(&self.to_locale, |locale| {
+                locale.find_text(hit.text.category, &hit.text.key)
+            })
+    }
+
+    /// has to be called before the app copies `text`, otherwise the watcher would search for it
+    pub fn note_own_copy(&self, text: &str) {
+        if let Some(watcher) = &self.clipboard_watcher {
+            watcher.ignore_own_copy(text);
+        }
+    }
+
+    /// puts the top translation into the system clipboard (button or Alt+Y, also from inside the game)
+    pub fn copy_top_translation(&mut self) {
+        let Some(translation) = self.get_top_translation() else {
+            return;
+        };
+        self.note_own_copy(&translation);
+
+        if self.clipboard.is_none() {
+            match arboard::Clipboard::new() {
+                Ok(clipboard) => self.clipboard = Some(clipboard),
+                Err(e) => {
+                    eprintln!("LanguageHelper - couldn't access the clipboard: {e}");
+                    return;
+                }
+            }
+        }
+        if let Some(clipboard) = &mut self.clipboard {
+            match clipboard.set_text(translation.as_str()) {
+                Ok(()) => println!("LanguageHelper - copied \"{translation}\""),
+                Err(e) => eprintln!("LanguageHelper - couldn't copy \"{translation}\": {e}"),
+            }
+        }
+    }
+
     pub fn get_search_prompt(&self) -> String {
         self.search_index.data_bridge.get_search_prompt()
     }
@@ -54,6 +154,76 @@ impl LanguageHelperSubsystem {
             .set_search_prompt(search_prompt);
     }
 
+    pub fn get_phrase(&self) -> String {
+        self.phrase.clone()
+    }
+
+    pub fn set_phrase(&mut self, phrase: String) {
+        self.phrase = phrase;
+    }
+
+    /// the phrase with every known name of the source locale translated into the target locale
+    pub fn get_phrase_translation(&self) -> Vec<PhraseSegment> {
+        if self.phrase.trim().is_empty() {
+            return Vec::new();
+        }
+
+        let mut cache = self.phrase_dictionary.borrow_mut();
+        let cache_key = (self.from_locale.clone(), self.locale_init_counter);
+        if cache.as_ref().is_none_or(|(key, _)| *key != cache_key) {
+            println!(
+                "LanguageHelper - building phrase dictionary for locale {}",
+                self.from_locale
+            );
+            let dictionary = self
+                .locale_subsystem
+                .with_all_locales(|locales| {
+                    let source_locales: Vec<&Locale> = locales
+                        .iter()
+                        .filter(|(key, _)| {
+                            self.is_searching_any_locale() || **key == self.from_locale
+                        })
+                        .map(|(_, locale)| *locale)
+                        .collect();
+                    PhraseDictionary::build(&source_locales)
+                })
+                .unwrap_or_default();
+            *cache = Some((cache_key, dictionary));
+        }
+
+        let Some((_, dictionary)) = cache.as_ref() else {
+            return Vec::new();
+        };
+        self.locale_subsystem
+            .with_locale(&self.to_locale, |target_locale| {
+                dictionary.translate(&self.phrase, target_locale)
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn is_category_searched(&self, category: TextCategory) -> bool {
+        self.search_index.data_bridge.is_category_searched(category)
+    }
+
+    pub fn set_category_searched(&mut self, category: TextCategory, searched: bool) {
+        self.search_index
+            .data_bridge
+            .set_category_searched(category, searched);
+    }
+
+    pub fn get_searched_categories(&self) -> Vec<TextCategory> {
+        TextCategory::ALL
+            .into_iter()
+            .filter(|category| self.is_category_searched(*category))
+            .collect()
+    }
+
+    pub fn set_searched_categories(&mut self, categories: &[TextCategory]) {
+        for category in TextCategory::ALL {
+            self.set_category_searched(category, categories.contains(&category));
+        }
+    }
+
     pub fn get_translation_target_locale(&self) -> &String {
         &self.to_locale
     }
@@ -84,8 +254,13 @@ impl LanguageHelperSubsystem {
         println!("LanguageHelper - set translation source locale to: {locale_key})");
     }
 
+    pub fn is_searching_any_locale(&self) -> bool {
+        self.from_locale == ANY_LOCALE
+    }
+
     pub fn swap_translation_locales(&mut self) {
-        if self.to_locale == self.from_locale {
+        // "any language" can't be translated into
+        if self.to_locale == self.from_locale || self.is_searching_any_locale() {
             return;
         }
         println!("LanguageHelper - swapping translation locales");
@@ -96,16 +271,72 @@ impl LanguageHelperSubsystem {
         self.set_translation_source_locale(cur_target);
     }
 
-    pub fn get_translation_pairs_for_search(&self) -> Vec<(TextCategory, String, String)> {
+    pub fn is_comparing_all_locales(&self) -> bool {
+        self.compare_all_locales
+    }
+
+    pub fn set_comparing_all_locales(&mut self, compare_all_locales: bool) {
+        self.compare_all_locales = compare_all_locales;
+    }
+
+    /// the text of `key` in every locale of the locale definition (sorted by locale key)
+    /// None where the locale isn't loaded or has no text for it
+    pub fn get_translations_in_all_locales(
+        &self,
+        category: TextCategory,
+        key: &str,
+    ) -> Vec<(String, Option<String>)> {
+        self.locale_subsystem
+            .get_defined_locales()
+            .into_iter()
+            .map(|locale_key| {
+                let text = self
+                    .locale_subsystem
+                    .with_locale(&locale_key, |locale| {
+                        locale.get_text(category, key).map(str::to_owned)
+                    })
+                    .flatten();
+                (locale_key, text)
+            })
+            .collect()
+    }
+
+    /// saves a user fix for the translation into the target locale, None resets it
+    pub fn set_translation_override(&self, pair: &TranslationPair, translation: Option<String>) {
+        if let Err(e) = self.locale_subsystem.set_user_override(
+            &self.to_locale,
+            pair.category,
+            &pair.key,
+            translation,
+        ) {
+            eprintln!("LanguageHelper - couldn't save the translation override, because {e}");
+        }
+    }
+
+    pub fn get_translation_pairs_for_search(&self) -> Vec<TranslationPair> {
         let mut source_texts = Vec::new();
+        let show_source_locale = self.is_searching_any_locale();
+        // getting the search results reads the locale data as well, so before locking it here
+        let search_results = self.search_index.get_search_results();
         let guard = self.locale_subsystem.data.read().unwrap();
 
         if let Some(data) = &*guard
             && let Some(target_locale) = data.locales.get(&self.to_locale)
         {
-            for text in self.search_index.get_search_results() {
-                let translation: String = target_locale.find_localized_text(&text.key);
-                source_texts.push((text.category, text.text.clone(), translation));
+            let user_overrides = data.user_overrides.get(&self.to_locale);
+            for hit in search_results {
+                let translation: String = target_locale.find_text(hit.text.category, &hit.text.key);
+                let overridden = user_overrides
+                    .and_then(|overrides| overrides.get(&hit.text.category))
+                    .is_some_and(|texts| texts.contains_key(&hit.text.key));
+                source_texts.push(TranslationPair {
+                    category: hit.text.category,
+                    key: hit.text.key.clone(),
+                    source_text: hit.text.text.clone(),
+                    translation,
+                    source_locale: show_source_locale.then(|| hit.locale_key.clone()),
+                    overridden,
+                });
             }
         };
 
@@ -1,11 +1,30 @@
 use crate::backend::{
-    locales::{Locale, LocaleSubsystem, LocalizedText},
+    locales::{Locale, LocaleSubsystem, LocalizedText, TextCategory},
     search::{
         data_bridge::DataBridge,
+        fuzzy::{self, MatchQuality},
         search_update::{UpdateRequest, UpdateRequestTracker},
     },
 };
-use std::{mem, rc::Rc, sync::Arc};
+use locale_data::normalize::normalize_prompt;
+use std::{collections::HashSet, mem, rc::Rc, sync::Arc};
+
+/// source "locale" which searches through every loaded locale at once
+pub const ANY_LOCALE: &str = "ANY";
+
+/// what the Language Helper searches through, until the user picks categories themselves
+pub const DEFAULT_SEARCH_CATEGORIES: [TextCategory; 4] = [
+    TextCategory::Monster,
+    TextCategory::Move,
+    TextCategory::Location,
+    TextCategory::Item,
+];
+
+/// a found text together with the locale it was found in
+pub struct SearchHit {
+    pub locale_key: String,
+    pub text: Arc<LocalizedText>,
+}
 
 pub struct LocaleDataBridge {
     update_tracker: UpdateRequestTracker,
@@ -15,6 +34,7 @@ pub struct LocaleDataBridge {
     // search configuration
     cur_search_prompt: String,
     search_locale: String,
+    search_categories: HashSet<TextCategory>,
 }
 
 impl LocaleDataBridge {
@@ -24,6 +44,7 @@ impl LocaleDataBridge {
             locale_subsystem,
             cur_search_prompt: "".into(),
             search_locale: "".into(),
+            search_categories: HashSet::from(DEFAULT_SEARCH_CATEGORIES),
         }
     }
 
@@ -39,8 +60,11 @@ impl LocaleDataBridge {
         // update cur_search_prompt and save old prompt without cloning
         let old_prompt = mem::replace(&mut self.cur_search_prompt, search_prompt);
 
-        // request incremental update only
-        if self.cur_search_prompt.starts_with(&old_prompt) {
+        // request incremental update only when a word was added, fuzzy matches of a word that's
+        // still being typed can show up later (more letters allow more typos)
+        if self.cur_search_prompt.starts_with(&old_prompt)
+            && old_prompt.ends_with(char::is_whitespace)
+        {
             self.update_tracker.request(UpdateRequest::Incremental);
             return;
         }
@@ -52,78 +76,110 @@ impl LocaleDataBridge {
         self.search_locale = locale_key;
         self.update_tracker.request(UpdateRequest::Full);
     }
+
+    pub fn is_category_searched(&self, category: TextCategory) -> bool {
+        self.search_categories.contains(&category)
+    }
+
+    pub fn set_category_searched(&mut self, category: TextCategory, searched: bool) {
+        let changed = match searched {
+            true => self.search_categories.insert(category),
+            false => self.search_categories.remove(&category),
+        };
+        if changed {
+            self.update_tracker.request(UpdateRequest::Full);
+        }
+    }
+
+    // the single search locale, or all of them in "any language" mode
+    fn with_search_locales<R>(&self, f: impl FnOnce(&[(&String, &Locale)]) -> R) -> Option<R> {
+        match self.search_locale == ANY_LOCALE {
+            true => self.locale_subsystem.with_all_locales(f),
+            false => self
+                .locale_subsystem
+                .with_locale(&self.search_locale, |locale| {
+                    f(&[(&self.search_locale, locale)])
+                }),
+        }
+    }
 }
 
 impl DataBridge for LocaleDataBridge {
-    type Item = LocalizedText;
+    type Item = SearchHit;
 
     fn get_all_potential_matches(&self) -> Vec<usize> {
-        self.locale_subsystem
-            .with_locale(&self.search_locale, |locale| {
-                locale
-                    .monsters
-                    .values()
-                    .cloned()
-                    .chain(locale.moves.values().cloned())
-                    .chain(locale.locations.values().cloned())
-                    .chain(locale.items.values().cloned())
-                    .collect()
-            })
-            .unwrap_or_default() // empty index if not found
+        self.with_search_locales(|locales| {
+            let mut offset = 0;
+            let mut matches = Vec::new();
+            for (_, locale) in locales {
+                matches.extend(
+                    locale
+                        .localized_texts
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, text)| self.search_categories.contains(&text.category))
+                        .map(|(i, _)| offset + i),
+                );
+                offset += locale.localized_texts.len();
+            }
+            matches
+        })
+        .unwrap_or_default() // empty index if not found
     }
 
     fn filter_matches(&self, cur_matches: Vec<usize>) -> Vec<usize> {
-        let mut search_list = cur_matches;
-        let normalized_prompt_tokens: Vec<String> = self
-            .cur_search_prompt
-            .split_whitespace()
-            .map(normalize_string)
-            .collect();
-
-        // custom whitespace-separated-tokens based filter function for QoL & performance
-        // also uses a char table to make e == è etc. (the same for many other letters)
-        // also checks starts_with against any word in multi-word names and multi-word prompts
-        let filter_search_list = |locale: &Locale, search_list: &mut Vec<usize>| {
-            search_list.retain(|index| match locale.localized_texts.get(*index) {
-                Some(text) => {
-                    let target_text_normalized = normalize_string(&text.text);
-                    let target_words: Vec<&str> =
-                        target_text_normalized.split_whitespace().collect();
-
-                    // all prompt words must have some starts_with match in the target text
-                    // i.e. "Punch Fir" matches "Fire Punch", but not "Ice Punch"
-                    normalized_prompt_tokens
-                        .iter()
-                        .all(|token| target_words.iter().any(|word| word.starts_with(token)))
-                }
-                None => false,
-            });
+        // uses a char table to make e == è etc. (the same for many other letters)
+        let normalized_prompt = normalize_prompt(&self.cur_search_prompt);
+
+        // every prompt word has to (nearly) start some word of the text, i.e. "Punch Fir" matches
+        // "Fire Punch", but not "Ice Punch" and "Garchmop" still finds "Garchomp"
+        // results are ranked: exact > prefix > word prefix > fuzzy, then by category
+        // the texts are normalized once when the locale is loaded (or come normalized in its bundle)
+        let rank_search_list = |locales: &[(&String, &Locale)]| {
+            let mut ranked: Vec<((MatchQuality, u8, usize), usize)> = cur_matches
+                .iter()
+                .filter_map(|i| {
+                    let (_, text) = find_text(locales, *i)?;
+                    let quality = match normalized_prompt.is_empty() {
+                        true => MatchQuality::WordPrefix, // everything, sorted by category
+                        false => fuzzy::match_quality(&normalized_prompt, &text.search_text)?,
+                    };
+                    let rank = (
+                        quality,
+                        category_weight(text.category),
+                        text.search_text.len(),
+                    );
+                    Some((rank, *i))
+                })
+                .collect();
+            ranked.sort_by_key(|(rank, _)| *rank);
+            ranked.into_iter().map(|(_, i)| i).collect()
         };
 
-        let found = self
-            .locale_subsystem
-            .with_locale(&self.search_locale, |locale| {
-                filter_search_list(locale, &mut search_list);
-            })
-            .is_some();
-
         // if for ANY reason no "search-locale" can't be found, search_index is emptied
-        if !found {
-            search_list.clear();
-        }
-
-        search_list
+        self.with_search_locales(rank_search_list)
+            .unwrap_or_default()
     }
 
-    fn get_items(&self, indices: &[usize]) -> Vec<Arc<LocalizedText>> {
-        self.locale_subsystem
-            .with_locale(&self.search_locale, |locale| {
-                indices
-                    .iter()
-                    .filter_map(|i| locale.localized_texts.get(*i).cloned())
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_default() // empty vec
+    // the same thing found in multiple languages ("Glurak", "Dracaufeu") is only listed once,
+    // in the language it matched best. not done while filtering, because the next letter typed
+    // might only match one of the others
+    fn get_items(&self, indices: &[usize]) -> Vec<Arc<SearchHit>> {
+        self.with_search_locales(|locales| {
+            let mut seen_keys = HashSet::new();
+            indices
+                .iter()
+                .filter_map(|i| find_text(locales, *i))
+                .filter(|(_, text)| seen_keys.insert(text.key.clone()))
+                .map(|(locale_key, text)| {
+                    Arc::new(SearchHit {
+                        locale_key: locale_key.clone(),
+                        text: text.clone(),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default() // empty vec
     }
 
     fn update_request(&mut self) -> &mut UpdateRequestTracker {
@@ -131,21 +187,27 @@ impl DataBridge for LocaleDataBridge {
     }
 }
 
-/// using this makes it easier to search for certain things in unfirmiliar languages or the damn Pokè
-fn normalize_char(c: char) -> String {
-    match c.to_ascii_lowercase() {
-        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a".to_string(),
-        'è' | 'é' | 'ê' | 'ë' => "e".to_string(),
-        'ì' | 'í' | 'î' | 'ï' => "i".to_string(),
-        'ò' | 'ó' | 'ô' | 'ö' | 'õ' => "o".to_string(),
-        'ù' | 'ú' | 'û' | 'ü' => "u".to_string(),
-        'ñ' => "n".to_string(),
-        'ç' => "c".to_string(),
-        'ß' => "ss".to_string(),
-        _ => c.to_ascii_lowercase().to_string(),
+// resolves an index of the search index to the locale & text it belongs to
+fn find_text<'a>(
+    locales: &[(&'a String, &'a Locale)],
+    mut index: usize,
+) -> Option<(&'a String, &'a Arc<LocalizedText>)> {
+    for (locale_key, locale) in locales {
+        match locale.localized_texts.get(index) {
+            Some(text) => return Some((locale_key, text)),
+            None => index -= locale.localized_texts.len(),
+        }
     }
+    None
 }
 
-fn normalize_string(s: &str) -> String {
-    s.chars().map(normalize_char).collect()
+/// pokemon are looked up the most, locations the least
+pub fn category_weight(category: TextCategory) -> u8 {
+    match category {
+        TextCategory::Monster => 0,
+        TextCategory::Move => 1,
+        TextCategory::Item => 2,
+        TextCategory::Location | TextCategory::PokedexLocation => 3,
+        TextCategory::ItemDescription | TextCategory::Miscellaneous => 4,
+    }
 }
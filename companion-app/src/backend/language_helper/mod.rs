@@ -1,2 +1,3 @@
 pub mod language_helper_feature;
 pub mod locale_data_bridge;
+pub mod phrase_translator;
@@ -0,0 +1,240 @@
+// translates whole chat lines ("WTS Leftovers and Timid Garchomp 5x31 pm me") by replacing every
+// known name with its translation, everything else is left as it was typed
+
+use super::locale_data_bridge::category_weight;
+use crate::backend::locales::{Locale, TextCategory};
+use locale_data::normalize::normalize_prompt;
+use std::{cmp::Reverse, collections::HashMap};
+
+/// a part of a translated phrase, either left as typed or replaced by a translation
+#[derive(Debug, Clone, PartialEq)]
+pub enum PhraseSegment {
+    Kept(String),
+    Translated {
+        original: String,
+        translation: String,
+        category: TextCategory,
+    },
+}
+
+struct PhraseTerm {
+    words: Vec<String>, // normalized
+    key: String,
+    category: TextCategory,
+}
+
+// a word of the phrase, the byte range excludes punctuation around it ("Garchomp," -> "Garchomp")
+struct PhraseWord {
+    start: usize,
+    end: usize,
+    normalized: String,
+}
+
+/// every name of the source locale(s), looked up by its first word
+#[derive(Default)]
+pub struct PhraseDictionary {
+    terms_by_first_word: HashMap<String, Vec<PhraseTerm>>, // longest terms first
+}
+
+impl PhraseDictionary {
+    pub fn build(locales: &[&Locale]) -> Self {
+        let mut terms_by_first_word: HashMap<String, Vec<PhraseTerm>> = HashMap::new();
+
+        for locale in locales {
+            // descriptions are sentences themselves, not something said in a chat line
+            let names = locale
+                .localized_texts
+                .iter()
+                .filter(|text| text.category != TextCategory::ItemDescription);
+
+            for text in names {
+                let words: Vec<String> = text.text.split_whitespace().map(normalize_word).collect();
+                let Some(first_word) = words.first() else {
+                    continue;
+                };
+                terms_by_first_word
+                    .entry(first_word.clone())
+                    .or_default()
+                    .push(PhraseTerm {
+                        words,
+                        key: text.key.clone(),
+                        category: text.category,
+                    });
+            }
+        }
+
+        // the same name in multiple categories ("Psychic") goes to the more common one
+        for terms in terms_by_first_word.values_mut() {
+            terms.sort_by_key(|term| (Reverse(term.words.len()), category_weight(term.category)));
+        }
+
+        Self {
+            terms_by_first_word,
+        }
+    }
+
+    /// replaces every known name with its translation from `target`, longest match first
+    /// ("Timid Nature Herb" is one item, not a nature followed by "Nature Herb")
+    pub fn translate(&self, phrase: &str, target: &Locale) -> Vec<PhraseSegment> {
+        let words = split_words(phrase);
+        let mut segments = Vec::new();
+        let mut kept_from = 0; // start of the phrase part that isn't in segments yet
+
+        let mut i = 0;
+        while i < words.len() {
+            let Some((term, translation)) = self.longest_match(&words[i..], target) else {
+                i += 1;
+                continue;
+            };
+
+            let start = words[i].start;
+            let end = words[i + term.words.len() - 1].end;
+            if kept_from < start {
+                segments.push(PhraseSegment::Kept(phrase[kept_from..start].to_owned()));
+            }
+            segments.push(PhraseSegment::Translated {
+                original: phrase[start..end].to_owned(),
+                translation: translation.to_owned(),
+                category: term.category,
+            });
+
+            kept_from = end;
+            i += term.words.len();
+        }
+
+        if kept_from < phrase.len() {
+            segments.push(PhraseSegment::Kept(phrase[kept_from..].to_owned()));
+        }
+        segments
+    }
+
+    // the longest term starting at the first word, which also has a translation
+    fn longest_match<'a>(
+        &'a self,
+        words: &[PhraseWord],
+        target: &'a Locale,
+    ) -> Option<(&'a PhraseTerm, &'a str)> {
+        self.terms_by_first_word
+            .get(&words.first()?.normalized)?
+            .iter()
+            .filter(|term| {
+                term.words.len() <= words.len()
+                    && term
+                        .words
+                        .iter()
+                        .zip(words)
+                        .all(|(term_word, word)| *term_word == word.normalized)
+            })
+            .find_map(|term| Some((term, target.get_text(term.category, &term.key)?)))
+    }
+}
+
+// punctuation around a word isn't part of the name ("Leftovers," or "(Garchomp)"), unless the word
+// is nothing else ("Egg & Item")
+fn trim_punctuation(word: &str) -> &str {
+    match word.trim_matches(|c: char| c.is_ascii_punctuation()) {
+        "" => word,
+        trimmed => trimmed,
+    }
+}
+
+fn normalize_word(word: &str) -> String {
+    normalize_prompt(trim_punctuation(word))
+}
+
+fn split_words(phrase: &str) -> Vec<PhraseWord> {
+    let mut words = Vec::new();
+    let mut offset = 0;
+
+    for part in phrase.split_inclusive(char::is_whitespace) {
+        let word = part.trim_end();
+        let trimmed = trim_punctuation(word);
+        if !trimmed.is_empty() {
+            // only punctuation comes before the trimmed word, so this finds the right spot
+            let start = offset + word.find(trimmed).unwrap_or(0);
+            words.push(PhraseWord {
+                start,
+                end: start + trimmed.len(),
+                normalized: normalize_prompt(trimmed),
+            });
+        }
+        offset += part.len();
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::locales::LocalizedText;
+    use std::sync::Arc;
+
+    fn test_locale(texts: &[(&str, &str, TextCategory)]) -> Locale {
+        let mut locale = Locale {
+            locale_name: String::new(),
+            localized_texts: Vec::new(),
+            monsters: HashMap::new(),
+            moves: HashMap::new(),
+            locations: HashMap::new(),
+            locations_pokedex: HashMap::new(),
+            items: HashMap::new(),
+            item_descriptions: HashMap::new(),
+            miscellaneous: HashMap::new(),
+            ids: HashMap::new(),
+        };
+        for (key, text, category) in texts {
+            let indices = match category {
+                TextCategory::Monster => &mut locale.monsters,
+                TextCategory::Item => &mut locale.items,
+                _ => &mut locale.miscellaneous,
+            };
+            indices.insert(key.to_string(), locale.localized_texts.len());
+            locale.localized_texts.push(Arc::new(LocalizedText::new(
+                *category,
+                key.to_string(),
+                text.to_string(),
+            )));
+        }
+        locale
+    }
+
+    #[test]
+    fn test_translate_phrase() {
+        let english = test_locale(&[
+            ("leftovers", "Leftovers", TextCategory::Item),
+            ("timid", "Timid", TextCategory::Miscellaneous),
+            ("timid nature herb", "Timid Nature Herb", TextCategory::Item),
+            ("garchomp", "Garchomp", TextCategory::Monster),
+        ]);
+        let german = test_locale(&[
+            ("leftovers", "Überreste", TextCategory::Item),
+            ("timid", "Scheu", TextCategory::Miscellaneous),
+            ("timid nature herb", "Scheu-Wesenskraut", TextCategory::Item),
+            ("garchomp", "Knakrack", TextCategory::Monster),
+        ]);
+        let dictionary = PhraseDictionary::build(&[&english]);
+
+        let translate = |phrase: &str| -> String {
+            dictionary
+                .translate(phrase, &german)
+                .into_iter()
+                .map(|segment| match segment {
+                    PhraseSegment::Kept(text) => text,
+                    PhraseSegment::Translated { translation, .. } => format!("[{translation}]"),
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            translate("WTS Leftovers and Timid Garchomp 5x31 pm me"),
+            "WTS [Überreste] and [Scheu] [Knakrack] 5x31 pm me"
+        );
+        // longest match first, punctuation and spacing stay as typed
+        assert_eq!(
+            translate("wtb  TIMID nature herb, (garchomp)!"),
+            "wtb  [Scheu-Wesenskraut], ([Knakrack])!"
+        );
+        assert_eq!(translate("timid nature"), "[Scheu] nature");
+        assert_eq!(translate(""), "");
+    }
+}
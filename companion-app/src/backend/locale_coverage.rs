@@ -0,0 +1,66 @@
+// report for translators: how complete every locale is and which keys need a look
+// (the data-builder has the same report as "coverage" subcommand for the built locale files)
+
+use crate::backend::locales::{Locale, TextCategory};
+use locale_data::text_coverage::{TextCoverage, compare_texts};
+
+/// the locale built from the (english) base data, every other locale is compared to it
+pub const BASE_LOCALE: &str = "EN";
+
+#[derive(Debug)]
+pub struct CategoryCoverage {
+    pub category: TextCategory,
+    pub texts: TextCoverage,
+}
+
+#[derive(Debug)]
+pub struct LocaleCoverage {
+    pub locale_key: String,
+    pub categories: Vec<CategoryCoverage>,
+}
+
+impl LocaleCoverage {
+    /// share of base keys with a (non-empty) text, 0.0 - 1.0
+    pub fn coverage(&self) -> f32 {
+        let base_count: usize = self.categories.iter().map(|c| c.texts.base_count).sum();
+        let translated: usize = self
+            .categories
+            .iter()
+            .map(|c| c.texts.translated_count())
+            .sum();
+        match base_count {
+            0 => 1.0,
+            n => translated as f32 / n as f32,
+        }
+    }
+
+    pub fn issue_count(&self) -> usize {
+        self.categories.iter().map(|c| c.texts.issue_count()).sum()
+    }
+}
+
+/// compares every locale to BASE_LOCALE, empty if that isn't loaded
+pub fn build_coverage_report(locales: &[(&String, &Locale)]) -> Vec<LocaleCoverage> {
+    let Some((_, base_locale)) = locales.iter().find(|(key, _)| *key == BASE_LOCALE) else {
+        eprintln!("LocaleCoverage - base locale {BASE_LOCALE} isn't loaded, no report");
+        return Vec::new();
+    };
+
+    locales
+        .iter()
+        .map(|(locale_key, locale)| LocaleCoverage {
+            locale_key: locale_key.to_string(),
+            categories: TextCategory::ALL
+                .into_iter()
+                .map(|category| CategoryCoverage {
+                    category,
+                    texts: compare_texts(
+                        &base_locale.get_category_texts(category),
+                        &locale.get_category_texts(category),
+                        *locale_key == BASE_LOCALE,
+                    ),
+                })
+                .collect(),
+        })
+        .collect()
+}
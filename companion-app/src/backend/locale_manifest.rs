@@ -0,0 +1,368 @@
+// manifest based locale downloads: only files that changed are fetched, every download is
+// verified against the manifest and a failed update leaves the old locale files in place
+// remote layout:
+//   <locale_url>/locale_manifest.json -> LocaleManifest (built by "data-builder manifest")
+//   <locale_url>/<file>               -> every file listed in it, e.g. "DE/monsters.json"
+
+use crate::{
+    backend::network::NetworkAccess,
+    utils::{download_to_path_verified, fetch_text, sha256_hex},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Component, Path},
+};
+
+pub const LOCALE_MANIFEST_FILE: &str = "locale_manifest.json";
+const STAGING_DIR: &str = ".staging";
+const BACKUP_DIR: &str = ".backup";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub size: u64,
+    pub sha256: String, // lowercase hex
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LocaleManifest {
+    pub files: BTreeMap<String, ManifestEntry>, // path relative to the locale dir -> entry
+}
+
+impl LocaleManifest {
+    /// files which are missing in `locales_dir` or differ from the manifest
+    pub fn changed_files(&self, locales_dir: &Path) -> Vec<String> {
+        self.files
+            .iter()
+            .filter(|(file, entry)| match fs::read(locales_dir.join(file)) {
+                Ok(content) => {
+                    content.len() as u64 != entry.size || sha256_hex(&content) != entry.sha256
+                }
+                Err(_) => true,
+            })
+            .map(|(file, _)| file.clone())
+            .collect()
+    }
+
+    fn entry(&self, file: &str) -> anyhow::Result<&ManifestEntry> {
+        self.files
+            .get(file)
+            .ok_or_else(|| anyhow::anyhow!("{file} is not in the locale manifest"))
+    }
+}
+
+pub async fn fetch_manifest(
+    network: &NetworkAccess,
+    base_url: &str,
+) -> anyhow::Result<LocaleManifest> {
+    let manifest: LocaleManifest = serde_json::from_str(
+        &fetch_text(network, &locale_url(base_url, LOCALE_MANIFEST_FILE)).await?,
+    )?;
+    for file in manifest.files.keys() {
+        validate_locale_file(file)?;
+    }
+    Ok(manifest)
+}
+
+/// downloads (and verifies) the given files straight into place, used for missing locales
+pub async fn download_files(
+    network: &NetworkAccess,
+    base_url: &str,
+    files: &[String],
+    locales_dir: &Path,
+) -> anyhow::Result<()> {
+    let manifest = fetch_manifest(network, base_url).await?;
+    download_manifest_files(network, base_url, &manifest, files, locales_dir).await
+}
+
+/// like download_files, with every file the manifest has in `dir` (e.g. "DE")
+pub async fn download_dir(
+    network: &NetworkAccess,
+    base_url: &str,
+    dir: &str,
+    locales_dir: &Path,
+) -> anyhow::Result<()> {
+    let manifest = fetch_manifest(network, base_url).await?;
+    let prefix = format!("{dir}/");
+    let files: Vec<String> = manifest
+        .files
+        .keys()
+        .filter(|file| file.starts_with(&prefix))
+        .cloned()
+        .collect();
+    if files.is_empty() {
+        anyhow::bail!("{dir} is not in the locale manifest");
+    }
+    download_manifest_files(network, base_url, &manifest, &files, locales_dir).await
+}
+
+async fn download_manifest_files(
+    network: &NetworkAccess,
+    base_url: &str,
+    manifest: &LocaleManifest,
+    files: &[String],
+    locales_dir: &Path,
+) -> anyhow::Result<()> {
+    for file in files {
+        let entry = manifest.entry(file)?;
+        download_to_path_verified(
+            network,
+            &locale_url(base_url, file),
+            locales_dir.join(file),
+            entry.size,
+            &entry.sha256,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// brings `locales_dir` up to date with the remote manifest and returns the updated files
+/// everything is downloaded into a staging folder first and the old files are only replaced
+/// once all downloads are verified, if replacing fails the old files are restored
+pub async fn update_locale_files(
+    network: &NetworkAccess,
+    base_url: &str,
+    locales_dir: &Path,
+) -> anyhow::Result<Vec<String>> {
+    let manifest = fetch_manifest(network, base_url).await?;
+    let changed_files = manifest.changed_files(locales_dir);
+    if changed_files.is_empty() {
+        return Ok(changed_files);
+    }
+    println!(
+        "LocaleSubsystem - {} locale files changed, downloading them ...",
+        changed_files.len()
+    );
+
+    let staging_dir = locales_dir.join(STAGING_DIR);
+    let backup_dir = locales_dir.join(BACKUP_DIR);
+    // leftovers of an earlier interrupted update
+    let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+    let _ = tokio::fs::remove_dir_all(&backup_dir).await;
+
+    let download_result = async {
+        for file in &changed_files {
+            let entry = manifest.entry(file)?;
+            download_to_path_verified(
+                network,
+                &locale_url(base_url, file),
+                staging_dir.join(file),
+                entry.size,
+                &entry.sha256,
+            )
+            .await?;
+        }
+        anyhow::Ok(())
+    }
+    .await;
+
+    let result = match download_result {
+        Ok(()) => commit_staged_files(&changed_files, &staging_dir, &backup_dir, locales_dir)
+            .map_err(anyhow::Error::from),
+        Err(e) => Err(e),
+    };
+
+    let _ = fs::remove_dir_all(&staging_dir);
+    let _ = fs::remove_dir_all(&backup_dir);
+    result.map(|_| changed_files)
+}
+
+// moves the staged files into place, on failure every already replaced file is rolled back
+fn commit_staged_files(
+    files: &[String],
+    staging_dir: &Path,
+    backup_dir: &Path,
+    locales_dir: &Path,
+) -> io::Result<()> {
+    for (i, file) in files.iter().enumerate() {
+        if let Err(e) = swap_in_file(file, staging_dir, backup_dir, locales_dir) {
+            eprintln!("LocaleSubsystem - couldn't replace {file} ({e}), rolling back the update");
+            restore_backups(&files[..=i], backup_dir, locales_dir);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+fn swap_in_file(
+    file: &str,
+    staging_dir: &Path,
+    backup_dir: &Path,
+    locales_dir: &Path,
+) -> io::Result<()> {
+    let dest = locales_dir.join(file);
+    if dest.exists() {
+        let backup = backup_dir.join(file);
+        if let Some(parent) = backup.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(&dest, &backup)?;
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(staging_dir.join(file), &dest)
+}
+
+// puts the old versions back, files that didn't exist before are removed again
+fn restore_backups(files: &[String], backup_dir: &Path, locales_dir: &Path) {
+    for file in files {
+        let dest = locales_dir.join(file);
+        let backup = backup_dir.join(file);
+        let _ = fs::remove_file(&dest);
+        if backup.exists()
+            && let Err(e) = fs::rename(&backup, &dest)
+        {
+            eprintln!("LocaleSubsystem - couldn't restore {file}, because {e}");
+        }
+    }
+}
+
+fn locale_url(base_url: &str, path: &str) -> String {
+    format!("{}/{path}", base_url.trim_end_matches('/'))
+}
+
+// manifest files must stay inside the locale dir
+fn validate_locale_file(file: &str) -> io::Result<()> {
+    let stays_inside = !file.is_empty()
+        && Path::new(file)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+    match stays_inside {
+        true => Ok(()),
+        false => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid locale file \"{file}\" in manifest"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::network::NetworkMode;
+    use crate::backend::test_utils::{serve, temp_dir};
+    use std::{collections::HashMap, path::PathBuf};
+
+    fn manifest_for(files: &[(&str, &str)]) -> String {
+        let manifest = LocaleManifest {
+            files: files
+                .iter()
+                .map(|(file, content)| {
+                    let entry = ManifestEntry {
+                        size: content.len() as u64,
+                        sha256: sha256_hex(content.as_bytes()),
+                    };
+                    (file.to_string(), entry)
+                })
+                .collect(),
+        };
+        serde_json::to_string(&manifest).unwrap()
+    }
+
+    fn temp_locales_dir(name: &str) -> PathBuf {
+        let dir = temp_dir(name);
+        fs::create_dir_all(dir.join("DE")).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_incremental_locale_update() {
+        let locales_dir = temp_locales_dir("locale_update");
+        fs::write(locales_dir.join("DE/monsters.json"), "{\"a\": \"A\"}").unwrap();
+        fs::write(locales_dir.join("DE/skills.json"), "old").unwrap();
+
+        let (base_url, requests) = serve(HashMap::from([
+            (
+                "/locale_manifest.json".to_owned(),
+                manifest_for(&[
+                    ("DE/monsters.json", "{\"a\": \"A\"}"),
+                    ("DE/skills.json", "new"),
+                ]),
+            ),
+            ("/DE/skills.json".to_owned(), "new".to_owned()),
+        ]));
+
+        let network = NetworkAccess::new(NetworkMode::Automatic);
+        let updated = update_locale_files(&network, &base_url, &locales_dir)
+            .await
+            .unwrap();
+        assert_eq!(updated, vec!["DE/skills.json"]);
+        assert_eq!(
+            fs::read_to_string(locales_dir.join("DE/skills.json")).unwrap(),
+            "new"
+        );
+        // the unchanged file was never requested
+        assert!(
+            !requests
+                .lock()
+                .unwrap()
+                .contains(&"/DE/monsters.json".to_owned())
+        );
+        assert!(!locales_dir.join(STAGING_DIR).exists());
+
+        let _ = fs::remove_dir_all(&locales_dir);
+    }
+
+    #[tokio::test]
+    async fn test_failed_verification_keeps_old_files() {
+        let locales_dir = temp_locales_dir("locale_verify");
+        fs::write(locales_dir.join("DE/monsters.json"), "old monsters").unwrap();
+        fs::write(locales_dir.join("DE/skills.json"), "old skills").unwrap();
+
+        let (base_url, _) = serve(HashMap::from([
+            (
+                "/locale_manifest.json".to_owned(),
+                manifest_for(&[
+                    ("DE/monsters.json", "new monsters"),
+                    ("DE/skills.json", "new skills"),
+                ]),
+            ),
+            ("/DE/monsters.json".to_owned(), "new monsters".to_owned()),
+            // tampered with / corrupted on the way
+            ("/DE/skills.json".to_owned(), "evil skills".to_owned()),
+        ]));
+
+        let network = NetworkAccess::new(NetworkMode::Automatic);
+        assert!(
+            update_locale_files(&network, &base_url, &locales_dir)
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            fs::read_to_string(locales_dir.join("DE/monsters.json")).unwrap(),
+            "old monsters"
+        );
+        assert_eq!(
+            fs::read_to_string(locales_dir.join("DE/skills.json")).unwrap(),
+            "old skills"
+        );
+        assert!(!locales_dir.join(STAGING_DIR).exists());
+
+        let _ = fs::remove_dir_all(&locales_dir);
+    }
+
+    #[test]
+    fn test_commit_rolls_back() {
+        let locales_dir = temp_locales_dir("locale_rollback");
+        let (staging_dir, backup_dir) =
+            (locales_dir.join(STAGING_DIR), locales_dir.join(BACKUP_DIR));
+        fs::write(locales_dir.join("DE/monsters.json"), "old monsters").unwrap();
+        fs::create_dir_all(staging_dir.join("DE")).unwrap();
+        fs::write(staging_dir.join("DE/monsters.json"), "new monsters").unwrap();
+        fs::write(staging_dir.join("DE/new_file.json"), "new file").unwrap();
+        // DE/skills.json is missing in staging, so replacing it fails
+
+        let files = ["DE/monsters.json", "DE/new_file.json", "DE/skills.json"].map(String::from);
+        assert!(commit_staged_files(&files, &staging_dir, &backup_dir, &locales_dir).is_err());
+        assert_eq!(
+            fs::read_to_string(locales_dir.join("DE/monsters.json")).unwrap(),
+            "old monsters"
+        );
+        assert!(!locales_dir.join("DE/new_file.json").exists());
+
+        let _ = fs::remove_dir_all(&locales_dir);
+    }
+}
@@ -0,0 +1,106 @@
+// user fixes for wrong or missing translations, one file per locale in the app's storage dir
+// merged over the shipped locale when it's loaded, so locale updates never touch them
+
+use crate::{app::APP_ID, backend::locales::TextCategory, utils::write_file_atomic};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+/// category -> key -> text, the contents of one override file
+pub type LocaleOverrides = BTreeMap<TextCategory, BTreeMap<String, String>>;
+
+pub fn overrides_dir() -> io::Result<PathBuf> {
+    match eframe::storage_dir(APP_ID) {
+        Some(storage_dir) => Ok(storage_dir.join("locale_overrides")),
+        None => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "there is no data/storage directory on your device",
+        )),
+    }
+}
+
+fn overrides_file(locale_key: &str) -> io::Result<PathBuf> {
+    Ok(overrides_dir()?.join(locale_key).with_extension("json"))
+}
+
+/// empty if there is no override file (yet) or it can't be read
+pub fn load_overrides(locale_key: &str) -> LocaleOverrides {
+    let Ok(path) = overrides_file(locale_key) else {
+        return LocaleOverrides::new();
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return LocaleOverrides::new(); // nothing overridden yet
+    };
+
+    match serde_json::from_str(&content) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("LocaleOverrides - ignoring broken override file {path:?}, because {e}");
+            LocaleOverrides::new()
+        }
+    }
+}
+
+/// None removes the override, so the shipped translation is used again
+/// returns all overrides of the locale after the change
+pub fn save_override(
+    locale_key: &str,
+    category: TextCategory,
+    key: &str,
+    text: Option<String>,
+) -> anyhow::Result<LocaleOverrides> {
+    let mut overrides = load_overrides(locale_key);
+    let category_overrides = overrides.entry(category).or_default();
+    match text {
+        Some(text) => category_overrides.insert(key.to_owned(), text),
+        None => category_overrides.remove(key),
+    };
+    overrides.retain(|_, texts| !texts.is_empty());
+
+    write_file_atomic(
+        &overrides_file(locale_key)?,
+        &serde_json::to_string_pretty(&overrides)?,
+    )?;
+    println!("LocaleOverrides - saved override for \"{key}\" ({locale_key})");
+    Ok(overrides)
+}
+
+/// all overrides (locale -> overrides) in the layout of the shipped locale files
+/// ({"DE": {"monsters.json": {"garchomp": "Knakrack"}}}), ready to be contributed upstream
+pub fn export_overrides(overrides: &BTreeMap<String, LocaleOverrides>) -> String {
+    let export: BTreeMap<&String, BTreeMap<&str, &BTreeMap<String, String>>> = overrides
+        .iter()
+        .filter(|(_, locale_overrides)| !locale_overrides.is_empty())
+        .map(|(locale_key, locale_overrides)| {
+            let files = locale_overrides
+                .iter()
+                .filter(|(_, texts)| !texts.is_empty())
+                .map(|(category, texts)| (category.locale_file_name(), texts))
+                .collect();
+            (locale_key, files)
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&export).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_file_formats() {
+        let overrides: LocaleOverrides =
+            serde_json::from_str(r#"{"Monster": {"garchomp": "Knakrack"}, "Item": {}}"#).unwrap();
+        assert_eq!(overrides[&TextCategory::Monster]["garchomp"], "Knakrack");
+
+        let all_overrides = BTreeMap::from([
+            ("DE".to_owned(), overrides),
+            ("FR".to_owned(), LocaleOverrides::new()),
+        ]);
+        let export: serde_json::Value =
+            serde_json::from_str(&export_overrides(&all_overrides)).unwrap();
+        assert_eq!(
+            export,
+            serde_json::json!({"DE": {"monsters.json": {"garchomp": "Knakrack"}}})
+        );
+    }
+}
@@ -1,8 +1,16 @@
-use super::async_manager::AsyncManager;
-use crate::utils::{download_to_path, find_asset_folder};
+use super::{
+    async_manager::AsyncManager,
+    locale_coverage::{LocaleCoverage, build_coverage_report},
+    locale_manifest::{download_dir, download_files, update_locale_files},
+    locale_overrides::{self, LocaleOverrides},
+    network::NetworkAccess,
+};
+use crate::utils::find_asset_folder;
+use locale_data::bundle::BUNDLE_FILE;
 use serde::{Deserialize, de::DeserializeOwned};
 use std::{
-    collections::HashMap,
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
     fs,
     io::{self, ErrorKind},
     path::{Path, PathBuf},
@@ -10,17 +18,13 @@ use std::{
     sync::{Arc, RwLock},
 };
 
-// TODO: global "automatic download" toggle, so it can be disabled
-const REPO_LOCALE_URL: &str = "https://raw.githubusercontent.com/Matzeall/PokeMMO-Companion/main/companion-app/assets/locales/";
-const LOCALE_FILES: [&str; 7] = [
-    "monsters.json",
-    "skills.json",
-    "locations.json",
-    "locations_pokedex.json",
-    "item_names.json",
-    "item_descriptions.json",
-    "miscellaneous.json",
-];
+// a single locale (texts, bundle, ids) lives in the locale-data crate, the data-builder writes its files
+pub use locale_data::locale::{Locale, LocalizedText, TextCategory};
+
+// the mirror url from the settings replaces it like every other default download (e.g. to test
+// against a local server), whether anything is downloaded at all is up to the network policy
+pub const DEFAULT_LOCALE_URL: &str = "https://raw.githubusercontent.com/Matzeall/PokeMMO-Companion/main/companion-app/assets/locales/";
+const LOCALE_DEFINITION_FILE: &str = "locale_definition.json";
 
 /// a definition of what locales there are (supposed to be), and what full display-name they have
 #[derive(Debug, Deserialize)]
@@ -29,185 +33,10 @@ pub struct LocalesDefinition {
     pub locales: HashMap<String, String>,
 }
 
-/// the type of list a localized text is from (and what category of thing it is)
-#[derive(Debug, Clone, Copy)]
-pub enum TextCategory {
-    Monster,
-    Move,
-    Location,
-    PokedexLocation, // contains time-information inseparable from main name
-    Item,
-    ItemDescription,
-    Miscellaneous,
-}
-
-#[derive(Debug)]
-pub struct LocalizedText {
-    pub key: String,
-    pub text: String,
-    pub category: TextCategory,
-}
-
-/// contains all the key-translation pairs for each catagory
-#[derive(Debug)]
-pub struct Locale {
-    #[allow(dead_code)]
-    pub locale_name: String,
-    pub localized_texts: Vec<Arc<LocalizedText>>,
-    pub monsters: HashMap<String, usize>,
-    pub moves: HashMap<String, usize>,
-    pub locations: HashMap<String, usize>,
-    pub locations_pokedex: HashMap<String, usize>,
-    pub items: HashMap<String, usize>,
-    pub item_descriptions: HashMap<String, usize>,
-    pub miscellaneous: HashMap<String, usize>,
-}
-
-impl Locale {
-    /// looks through any dictionary in order of importance and returns key back when it doesn't find anything
-    pub fn find_localized_text(&self, key: &str) -> String {
-        let mut index: usize = 0;
-        let mut found: bool = false;
-
-        if let Some(i) = self.monsters.get(key) {
-            index = *i;
-            found = true;
-        } else if let Some(i) = self.moves.get(key) {
-            index = *i;
-            found = true;
-        } else if let Some(i) = self.locations.get(key) {
-            index = *i;
-            found = true;
-        } else if let Some(i) = self.locations_pokedex.get(key) {
-            index = *i;
-            found = true;
-        } else if let Some(i) = self.items.get(key) {
-            index = *i;
-            found = true;
-        } else if let Some(i) = self.item_descriptions.get(key) {
-            index = *i;
-            found = true;
-        } else if let Some(i) = self.miscellaneous.get(key) {
-            index = *i;
-            found = true;
-        }
-
-        if found
-            && let Some(loc_text) = self.localized_texts.get(index)
-            && !loc_text.text.is_empty()
-        {
-            return loc_text.text.clone();
-        }
-
-        key.into()
-    }
-
-    /// errors when the parse function for any file fails
-    fn parse_from_dir(dir: impl AsRef<Path>, locale_name: String) -> io::Result<Self> {
-        let dir_path = dir.as_ref();
-
-        // parse files into HashMap<key,text>
-        let monsters: HashMap<String, String> = parse_json_file(dir_path.join("monsters.json"))?;
-        let moves: HashMap<String, String> = parse_json_file(dir_path.join("skills.json"))?;
-        let locations: HashMap<String, String> = parse_json_file(dir_path.join("locations.json"))?;
-        let locations_pokedex: HashMap<String, String> =
-            parse_json_file(dir_path.join("locations_pokedex.json"))?;
-        let items: HashMap<String, String> = parse_json_file(dir_path.join("item_names.json"))?;
-        let item_descriptions: HashMap<String, String> =
-            parse_json_file(dir_path.join("item_descriptions.json"))?;
-        let miscellaneous: HashMap<String, String> =
-            parse_json_file(dir_path.join("miscellaneous.json"))?;
-
-        // build flat localized_texts list and build hashmaps<key -> index>
-        let mut localized_texts = Vec::new();
-
-        let mut monster_indices = HashMap::new();
-        for (key, text) in monsters {
-            monster_indices.insert(key.clone(), localized_texts.len());
-            localized_texts.push(Arc::new(LocalizedText {
-                key,
-                text,
-                category: TextCategory::Monster,
-            }));
-        }
-
-        let mut moves_indices = HashMap::new();
-        for (key, text) in moves {
-            moves_indices.insert(key.clone(), localized_texts.len());
-            localized_texts.push(Arc::new(LocalizedText {
-                key,
-                text,
-                category: TextCategory::Move,
-            }));
-        }
-
-        let mut locations_indices = HashMap::new();
-        for (key, text) in locations {
-            locations_indices.insert(key.clone(), localized_texts.len());
-            localized_texts.push(Arc::new(LocalizedText {
-                key,
-                text,
-                category: TextCategory::Location,
-            }));
-        }
-
-        let mut pokedex_location_indices = HashMap::new();
-        for (key, text) in locations_pokedex {
-            pokedex_location_indices.insert(key.clone(), localized_texts.len());
-            localized_texts.push(Arc::new(LocalizedText {
-                key,
-                text,
-                category: TextCategory::PokedexLocation,
-            }));
-        }
-
-        let mut item_indices = HashMap::new();
-        for (key, text) in items {
-            item_indices.insert(key.clone(), localized_texts.len());
-            localized_texts.push(Arc::new(LocalizedText {
-                key,
-                text,
-                category: TextCategory::Item,
-            }));
-        }
-
-        let mut item_description_indices = HashMap::new();
-        for (key, text) in item_descriptions {
-            item_description_indices.insert(key.clone(), localized_texts.len());
-            localized_texts.push(Arc::new(LocalizedText {
-                key,
-                text,
-                category: TextCategory::ItemDescription,
-            }));
-        }
-
-        let mut miscellaneous_indices = HashMap::new();
-        for (key, text) in miscellaneous {
-            miscellaneous_indices.insert(key.clone(), localized_texts.len());
-            localized_texts.push(Arc::new(LocalizedText {
-                key,
-                text,
-                category: TextCategory::Miscellaneous,
-            }));
-        }
-
-        Ok(Self {
-            locale_name,
-            localized_texts,
-            monsters: monster_indices,
-            moves: moves_indices,
-            locations: locations_indices,
-            locations_pokedex: pokedex_location_indices,
-            items: item_indices,
-            item_descriptions: item_description_indices,
-            miscellaneous: miscellaneous_indices,
-        })
-    }
-}
-
 pub struct LocaleData {
     pub locale_definition: LocalesDefinition,
     pub locales: HashMap<String, Locale>, // all dictionaries loaded in memory
+    pub user_overrides: BTreeMap<String, LocaleOverrides>, // already merged into locales
 }
 
 pub struct LocaleSubsystem {
@@ -216,19 +45,21 @@ pub struct LocaleSubsystem {
     pub init_counter: Arc<RwLock<usize>>, // incremented when data is re-initialized
 
     async_manager: Rc<AsyncManager>,
+    network: NetworkAccess,
+    // comparing every locale takes a moment, so only once per initialization
+    coverage_report: RefCell<Option<(usize, Rc<Vec<LocaleCoverage>>)>>,
 }
 
 impl LocaleSubsystem {
-    pub fn new(async_manager: Rc<AsyncManager>) -> LocaleSubsystem {
-        let subsystem = LocaleSubsystem {
+    /// doesn't load anything yet, trigger_initialization once the network policy is known
+    pub fn new(async_manager: Rc<AsyncManager>, network: NetworkAccess) -> LocaleSubsystem {
+        LocaleSubsystem {
             data: Arc::new(RwLock::new(None)),
             init_counter: Arc::new(RwLock::new(0)),
             async_manager: async_manager.clone(),
-        };
-
-        subsystem.trigger_initialization();
-
-        subsystem
+            network,
+            coverage_report: RefCell::new(None),
+        }
     }
 
     pub fn trigger_initialization(&self) {
@@ -236,25 +67,25 @@ impl LocaleSubsystem {
 
         let data_ref = self.data.clone();
         let counter_ref = self.init_counter.clone();
+        let network = self.network.clone();
         // spawn_unique prevents multiple inits at once
         self.async_manager
             .spawn_unique("LocaleSubsystem_Init", async move {
-                reload_subsystem_data(data_ref, counter_ref).await;
+                reload_subsystem_data(data_ref, counter_ref, &network).await;
             });
     }
 
     pub fn trigger_locale_update(&self) {
-        let cur_version = self.get_locale_definition_version();
-        println!(
-            "LocaleSubsystem - begin asynchronous locale update (current version: {cur_version})"
-        );
+        println!("LocaleSubsystem - begin asynchronous locale update");
 
         let data_ref = self.data.clone();
         let counter_ref = self.init_counter.clone();
+        let network = self.network.clone();
         self.async_manager
             .spawn_unique("LocaleSubsystem_Update", async move {
-                match update_locales(cur_version).await {
-                    Ok(()) => reload_subsystem_data(data_ref, counter_ref).await,
+                match update_locales(&network).await {
+                    Ok(true) => reload_subsystem_data(data_ref, counter_ref, &network).await,
+                    Ok(false) => println!("LocaleSubsystem - already up-to-date locales"),
                     Err(e) => eprintln!("LocaleSubsystem - Update failed because, {e}"),
                 };
             });
@@ -274,6 +105,20 @@ impl LocaleSubsystem {
         }
     }
 
+    /// every locale of the locale definition, loaded or not (sorted by key)
+    pub fn get_defined_locales(&self) -> Vec<String> {
+        let guard = self.data.read().unwrap();
+        match &*guard {
+            None => Vec::new(),
+            Some(data) => {
+                let mut keys: Vec<String> =
+                    data.locale_definition.locales.keys().cloned().collect();
+                keys.sort();
+                keys
+            }
+        }
+    }
+
     pub fn get_locale_display_name(&self, key: &str) -> String {
         let guard = self.data.read().unwrap();
         match &*guard {
@@ -297,6 +142,85 @@ impl LocaleSubsystem {
         None
     }
 
+    /// like with_locale, but with every loaded locale (sorted by key, so the order is stable)
+    pub fn with_all_locales<R>(&self, f: impl FnOnce(&[(&String, &Locale)]) -> R) -> Option<R> {
+        let guard = self.data.read().unwrap();
+        let data = (*guard).as_ref()?;
+
+        let mut locales: Vec<(&String, &Locale)> = data.locales.iter().collect();
+        locales.sort_by_key(|(key, _)| *key);
+        Some(f(&locales))
+    }
+
+    /// how complete every loaded locale is compared to the base locale
+    pub fn get_coverage_report(&self) -> Rc<Vec<LocaleCoverage>> {
+        let init_counter = *self.init_counter.read().unwrap();
+        let mut cache = self.coverage_report.borrow_mut();
+        if let Some((counter, report)) = &*cache
+            && *counter == init_counter
+        {
+            return report.clone();
+        }
+
+        let report = Rc::new(
+            self.with_all_locales(build_coverage_report)
+                .unwrap_or_default(),
+        );
+        *cache = Some((init_counter, report.clone()));
+        report
+    }
+
+    /// saves a user fix for a translation (None removes it again) and applies it right away
+    pub fn set_user_override(
+        &self,
+        locale_key: &str,
+        category: TextCategory,
+        key: &str,
+        text: Option<String>,
+    ) -> anyhow::Result<()> {
+        let overrides = locale_overrides::save_override(locale_key, category, key, text.clone())?;
+
+        let Some(text) = text else {
+            // the shipped text has to be read from disk again
+            self.trigger_initialization();
+            return Ok(());
+        };
+
+        let mut data_guard = self.data.write().unwrap();
+        if let Some(data) = &mut *data_guard {
+            if let Some(locale) = data.locales.get_mut(locale_key) {
+                locale.set_text(category, key, &text);
+            }
+            data.user_overrides.insert(locale_key.to_owned(), overrides);
+        }
+        // everything built from the locale data has to be refreshed
+        *self.init_counter.write().unwrap() += 1;
+        Ok(())
+    }
+
+    /// (locale key, number of overridden texts) for every locale with user overrides
+    pub fn get_user_override_counts(&self) -> Vec<(String, usize)> {
+        let guard = self.data.read().unwrap();
+        match &*guard {
+            None => Vec::new(),
+            Some(data) => data
+                .user_overrides
+                .iter()
+                .map(|(key, overrides)| (key.clone(), overrides.values().map(|t| t.len()).sum()))
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+        }
+    }
+
+    /// all user overrides as json, to contribute them upstream
+    pub fn export_user_overrides(&self) -> String {
+        let guard = self.data.read().unwrap();
+        match &*guard {
+            None => String::new(),
+            Some(data) => locale_overrides::export_overrides(&data.user_overrides),
+        }
+    }
+
     pub fn get_locale_definition_version(&self) -> u8 {
         let guard = self.data.read().unwrap();
 
@@ -335,7 +259,20 @@ fn load_locale_definition_from_disk() -> io::Result<LocalesDefinition> {
     Ok(definition)
 }
 
-async fn load_data() -> anyhow::Result<LocaleData> {
+/// locale keys (e.g. "DE") of the locale definition on disk, empty if it wasn't downloaded yet
+/// for synchronous users, which can't wait for the LocaleSubsystem to initialize
+pub fn read_locale_keys_from_disk() -> Vec<String> {
+    match load_locale_definition_from_disk() {
+        Ok(definition) => {
+            let mut keys: Vec<String> = definition.locales.into_keys().collect();
+            keys.sort();
+            keys
+        }
+        Err(_) => Vec::new(),
+    }
+}
+
+async fn load_data(network: &NetworkAccess) -> anyhow::Result<LocaleData> {
     let def = match load_locale_definition_from_disk() {
         Ok(s) => s,
         Err(e) if e.kind() == io::ErrorKind::NotFound => {
@@ -343,9 +280,8 @@ async fn load_data() -> anyhow::Result<LocaleData> {
                 "LocaleSubsystem - locale_definition.json not found locally, starting automatic download ..."
             );
             // download locale definition from github and try again
-            let destination = get_locale_definition_path()?;
-            let url = REPO_LOCALE_URL.to_owned() + "locale_definition.json";
-            download_to_path(&url, destination).await?;
+            let files = [LOCALE_DEFINITION_FILE.to_owned()];
+            download_files(network, DEFAULT_LOCALE_URL, &files, &get_locale_dir_path()?).await?;
 
             // try loading from disk again
             load_locale_definition_from_disk()?
@@ -360,18 +296,18 @@ async fn load_data() -> anyhow::Result<LocaleData> {
 
     for (key, name) in &def.locales {
         let directory = locales_dir.join(key);
-        if let Ok(locale) = Locale::parse_from_dir(&directory, name.clone()) {
+        if let Ok(locale) = Locale::load_from_dir(&directory, name.clone()) {
             locales.insert(key.clone(), locale);
         } else {
             // downloads each file from the repo again, overwriting existing ones
-            if let Err(e) = download_locale(key).await {
+            if let Err(e) = download_locale(network, key).await {
                 eprintln!(
                     "LocaleSubsystem - couldn't download locale ({key} - {name}), because {e}"
                 );
                 continue;
             };
             // try parsing again, print error when necessary, but don't bubble up error
-            match Locale::parse_from_dir(&directory, name.clone()) {
+            match Locale::load_from_dir(&directory, name.clone()) {
                 Ok(locale) => {
                     locales.insert(key.clone(), locale);
                 }
@@ -386,19 +322,32 @@ async fn load_data() -> anyhow::Result<LocaleData> {
         println!("LocaleSubsystem - {name} was loaded successfully");
     }
 
+    // user fixes go over the shipped translations
+    let mut user_overrides = BTreeMap::new();
+    for (key, locale) in &mut locales {
+        let overrides = locale_overrides::load_overrides(key);
+        if !overrides.is_empty() {
+            println!("LocaleSubsystem - applying user overrides for {key}");
+            locale.apply_overrides(&overrides);
+            user_overrides.insert(key.clone(), overrides);
+        }
+    }
+
     println!("LocaleSubsystem - finished loading locales");
 
     Ok(LocaleData {
         locale_definition: def,
         locales,
+        user_overrides,
     })
 }
 
 async fn reload_subsystem_data(
     data_ref: Arc<RwLock<Option<LocaleData>>>,
     counter_ref: Arc<RwLock<usize>>,
+    network: &NetworkAccess,
 ) {
-    let result = load_data().await;
+    let result = load_data(network).await;
     let mut data_guard = data_ref.write().unwrap();
     let mut counter_guard = counter_ref.write().unwrap();
     match result {
@@ -415,43 +364,32 @@ async fn reload_subsystem_data(
     };
 }
 
-async fn update_locales(current_version: u8) -> anyhow::Result<()> {
-    // download most current locale_definition, overwriting
-    let destination = get_locale_definition_path()?;
-    let url = REPO_LOCALE_URL.to_owned() + "locale_definition.json";
-    download_to_path(&url, destination.clone()).await?;
-
-    // parse downloaded definition
-    let downloaded_definition: LocalesDefinition = parse_json_file(&destination)?;
-
-    if current_version >= downloaded_definition.version {
-        println!("LocaleSubsystem - already up-to-date locales");
-        return Ok(()); // everything up-to-date
-    }
-
-    // if version was bumped, then re-download all locales
-    for (key, name) in downloaded_definition.locales {
-        if let Err(e) = download_locale(&key).await {
-            eprintln!("LocaleSubsystem - couldn't download locale ({key} - {name}), because {e}");
-            continue;
-        };
+/// fetches every locale file that differs from the remote manifest, true if anything changed
+/// a failed update keeps the old files, so the loaded locales stay usable
+async fn update_locales(network: &NetworkAccess) -> anyhow::Result<bool> {
+    let locales_dir = get_locale_dir_path()?;
+    let updated_files = update_locale_files(network, DEFAULT_LOCALE_URL, &locales_dir).await?;
+    for file in &updated_files {
+        println!("LocaleSubsystem - updated {file}");
+        // bundles only come with releases, the updated json files are loaded instead
+        if let Some((locale_key, _)) = file.split_once('/') {
+            let _ = fs::remove_file(locales_dir.join(locale_key).join(BUNDLE_FILE));
+        }
     }
 
-    Ok(())
+    Ok(!updated_files.is_empty())
 }
 
-async fn download_locale(locale_key: &str) -> anyhow::Result<()> {
+async fn download_locale(network: &NetworkAccess, locale_key: &str) -> anyhow::Result<()> {
     println!("LocaleSubsystem - downloading locale ({locale_key})");
-    let base_dir = get_locale_dir_path()?;
-    let locale_dir = base_dir.join(locale_key);
-    let locale_url = REPO_LOCALE_URL.to_owned() + locale_key + "/";
-
-    for filename in LOCALE_FILES {
-        let file_url: String = locale_url.clone() + filename;
-        download_to_path(&file_url, locale_dir.join(filename)).await?;
-    }
-
-    Ok(())
+    // every file the manifest has for it, the optional ones (bundle, ids) included
+    download_dir(
+        network,
+        DEFAULT_LOCALE_URL,
+        locale_key,
+        &get_locale_dir_path()?,
+    )
+    .await
 }
 
 fn parse_json_file<T>(path: impl AsRef<Path>) -> io::Result<T>
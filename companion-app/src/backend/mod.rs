@@ -1,10 +1,25 @@
 pub mod async_manager;
+pub mod clipboard_watcher;
 pub mod feature_state;
+pub mod game_data;
 pub mod language_helper;
+pub mod locale_coverage;
+pub mod locale_manifest;
+pub mod locale_overrides;
 pub mod locales;
+pub mod network;
 pub mod notes_feature;
+pub mod resource_cards;
+pub mod resource_editor;
+pub mod resource_linter;
+pub mod resource_packs;
+pub mod resource_parser;
+pub mod resource_tables;
 pub mod ressources_feature;
 pub mod search;
 pub mod settings;
 pub mod storage;
+#[cfg(test)]
+pub mod test_utils;
 pub mod type_matrix_feature;
+pub mod ui_strings;
@@ -0,0 +1,317 @@
+// every download of the app goes through a NetworkAccess, so the network policy from the settings
+// (offline / ask first / automatic + an optional mirror) applies everywhere
+// the handle is cheap to clone and moved into the async tasks doing the requests
+
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+/// everything the app downloads by default lives below this url, a mirror replaces it
+pub const DEFAULT_DATA_URL: &str =
+    "https://raw.githubusercontent.com/Matzeall/PokeMMO-Companion/main/";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_ATTEMPTS: u32 = 3;
+const FIRST_RETRY_DELAY: Duration = Duration::from_millis(500); // doubled on every retry
+const APPROVAL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NetworkMode {
+    Offline,
+    AskFirst,
+    #[default]
+    Automatic,
+}
+
+impl NetworkMode {
+    pub const ALL: [NetworkMode; 3] = [
+        NetworkMode::Offline,
+        NetworkMode::AskFirst,
+        NetworkMode::Automatic,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            NetworkMode::Offline => "Offline",
+            NetworkMode::AskFirst => "Ask first",
+            NetworkMode::Automatic => "Automatic",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            NetworkMode::Offline => "never connects to anything, only local data is used",
+            NetworkMode::AskFirst => "asks once per session before the first download",
+            NetworkMode::Automatic => "downloads missing data and updates when needed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Approval {
+    Undecided,
+    Allowed, // for the rest of the session (or until the mode changes)
+    Denied,  // only for the requests waiting right now
+}
+
+struct NetworkState {
+    mode: NetworkMode,
+    mirror_url: String, // empty -> DEFAULT_DATA_URL
+    approval: Approval,
+    waiting: Vec<String>, // urls waiting for the user's answer in AskFirst mode
+}
+
+#[derive(Clone)]
+pub struct NetworkAccess {
+    state: Arc<RwLock<NetworkState>>,
+    client: reqwest::Client,
+}
+
+impl NetworkAccess {
+    pub fn new(mode: NetworkMode) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|e| {
+                eprintln!("Network - couldn't configure http client ({e}), using defaults");
+                reqwest::Client::new()
+            });
+
+        Self {
+            state: Arc::new(RwLock::new(NetworkState {
+                mode,
+                mirror_url: String::new(),
+                approval: Approval::Undecided,
+                waiting: Vec::new(),
+            })),
+            client,
+        }
+    }
+
+    /// applies the settings, called every frame
+    pub fn configure(&self, mode: NetworkMode, mirror_url: &str) {
+        let mut state = self.state.write().unwrap();
+        if state.mode != mode {
+            println!("Network - switching to {} mode", mode.display_name());
+            state.mode = mode;
+            state.approval = Approval::Undecided; // a new "ask first" asks again
+        }
+        if state.mirror_url != mirror_url.trim() {
+            state.mirror_url = mirror_url.trim().to_owned();
+        }
+    }
+
+    /// urls that wait for the user to allow network access
+    pub fn get_waiting_requests(&self) -> Vec<String> {
+        self.state.read().unwrap().waiting.clone()
+    }
+
+    /// the user's answer to the "ask first" prompt
+    pub fn answer_waiting_requests(&self, allowed: bool) {
+        let mut state = self.state.write().unwrap();
+        if !state.waiting.is_empty() {
+            println!(
+                "Network - user {} network access",
+                if allowed { "allowed" } else { "denied" }
+            );
+            state.approval = if allowed {
+                Approval::Allowed
+            } else {
+                Approval::Denied
+            };
+        }
+    }
+
+    /// the url actually requested, default data urls are redirected to the mirror
+    pub fn resolve_url(&self, url: &str) -> String {
+        let state = self.state.read().unwrap();
+        match url.strip_prefix(DEFAULT_DATA_URL) {
+            Some(path) if !state.mirror_url.is_empty() => {
+                format!("{}/{path}", state.mirror_url.trim_end_matches('/'))
+            }
+            _ => url.to_owned(),
+        }
+    }
+
+    /// GET with retries, fails right away when the network policy doesn't allow it
+    /// reading the body (`read_body`) is part of every attempt, so a download that breaks off
+    /// mid-stream is requested again (from the mirror, if one is set) and read from the start
+    pub async fn get<T, F, Fut>(&self, url: &str, mut read_body: F) -> anyhow::Result<T>
+    where
+        F: FnMut(reqwest::Response) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let url = self.resolve_url(url);
+        self.wait_for_access(&url).await?;
+
+        let mut attempt = 1;
+        loop {
+            let result = match self
+                .client
+                .get(&url)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+            {
+                Ok(response) => read_body(response).await,
+                Err(e) => Err(e.into()),
+            };
+            match result {
+                Ok(body) => return Ok(body),
+                Err(e) if attempt < MAX_ATTEMPTS && is_retryable(&e) => {
+                    let delay = FIRST_RETRY_DELAY * 2u32.pow(attempt - 1);
+                    eprintln!("Network - {url} failed ({e}), retrying in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn wait_for_access(&self, url: &str) -> anyhow::Result<()> {
+        {
+            let mut state = self.state.write().unwrap();
+            match (state.mode, state.approval) {
+                (NetworkMode::Offline, _) => {
+                    anyhow::bail!("offline mode is enabled, didn't request {url}")
+                }
+                (NetworkMode::Automatic, _) | (NetworkMode::AskFirst, Approval::Allowed) => {
+                    return Ok(());
+                }
+                _ => {
+                    println!("Network - waiting for permission to request {url}");
+                    state.waiting.push(url.to_owned());
+                }
+            }
+        }
+
+        loop {
+            tokio::time::sleep(APPROVAL_POLL_INTERVAL).await;
+            let mut state = self.state.write().unwrap();
+            let answer = match (state.mode, state.approval) {
+                (NetworkMode::Automatic, _) | (_, Approval::Allowed) => Some(Ok(())),
+                (NetworkMode::Offline, _) => Some(Err(anyhow::anyhow!(
+                    "offline mode is enabled, didn't request {url}"
+                ))),
+                (_, Approval::Denied) => {
+                    Some(Err(anyhow::anyhow!("network access to {url} was denied")))
+                }
+                (_, Approval::Undecided) => None,
+            };
+
+            if let Some(answer) = answer {
+                if let Some(i) = state.waiting.iter().position(|u| u == url) {
+                    state.waiting.remove(i);
+                }
+                // a denial only answers the requests waiting right now
+                if state.waiting.is_empty() && state.approval == Approval::Denied {
+                    state.approval = Approval::Undecided;
+                }
+                return answer;
+            }
+        }
+    }
+}
+
+// not found and the like won't change by asking again, neither do local errors (e.g. disk full)
+fn is_retryable(e: &anyhow::Error) -> bool {
+    let Some(e) = e.downcast_ref::<reqwest::Error>() else {
+        return false;
+    };
+    match e.status() {
+        Some(status) => status.is_server_error() || status.as_u16() == 429,
+        None => e.is_timeout() || e.is_connect() || e.is_request() || e.is_body(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::test_utils::{serve, serve_flaky, temp_dir};
+    use crate::utils::{download_to_path, fetch_text};
+    use std::{collections::HashMap, fs};
+
+    #[test]
+    fn test_default_urls_follow_the_mirror() {
+        // otherwise a mirror wouldn't replace them
+        assert!(crate::backend::locales::DEFAULT_LOCALE_URL.starts_with(DEFAULT_DATA_URL));
+    }
+
+    #[tokio::test]
+    async fn test_network_policy() {
+        let network = NetworkAccess::new(NetworkMode::Offline);
+        assert!(fetch_text(&network, "http://127.0.0.1:1/").await.is_err());
+
+        network.configure(NetworkMode::Automatic, "http://localhost:8000/");
+        assert_eq!(
+            network.resolve_url(&format!("{DEFAULT_DATA_URL}resource_packs/index.json")),
+            "http://localhost:8000/resource_packs/index.json"
+        );
+        assert_eq!(
+            network.resolve_url("https://example.com/a.json"),
+            "https://example.com/a.json"
+        );
+
+        // denying answers every waiting request, the next one asks again
+        network.configure(NetworkMode::AskFirst, "");
+        let waiting = tokio::spawn({
+            let network = network.clone();
+            async move { fetch_text(&network, "http://127.0.0.1:1/").await }
+        });
+        while network.get_waiting_requests().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        network.answer_waiting_requests(false);
+        let error = waiting.await.unwrap().unwrap_err();
+        assert!(error.to_string().contains("denied"));
+        assert!(network.get_waiting_requests().is_empty());
+        assert_eq!(network.state.read().unwrap().approval, Approval::Undecided);
+    }
+
+    #[tokio::test]
+    async fn test_broken_off_download_is_retried() {
+        // the first answer breaks off mid-body
+        let (mirror_url, requests) = serve_flaky(HashMap::from([(
+            "/companion-app/assets/locales/DE/skills.json",
+            r#"{"metronome": "Metronom"}"#,
+        )]));
+        let network = NetworkAccess::new(NetworkMode::Automatic);
+        network.configure(NetworkMode::Automatic, &mirror_url);
+
+        let text = fetch_text(
+            &network,
+            &format!(
+                "{}DE/skills.json",
+                crate::backend::locales::DEFAULT_LOCALE_URL
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(text, r#"{"metronome": "Metronom"}"#);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_download_leaves_no_tmp_file() {
+        let (base_url, _) = serve(HashMap::from([("/skills.json", "{}")]));
+        let network = NetworkAccess::new(NetworkMode::Automatic);
+        let dir = temp_dir("failed_download");
+        // a (non-empty) directory is in the way, so the download can't be moved into place
+        let dest = dir.join("skills.json");
+        fs::create_dir_all(dest.join("blocked")).unwrap();
+
+        let url = format!("{base_url}skills.json");
+        assert!(
+            download_to_path(&network, &url, dest.clone())
+                .await
+                .is_err()
+        );
+        assert!(!dest.with_extension("tmp").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,336 @@
+// data-bound resources, which render live data cards instead of static markdown
+// e.g. <Pokemon=Ho-Oh>, <Item=Leftovers>, <Move=Surf>, <Location=Viridian Forest[, Kanto]>
+
+use egui::{Color32, Frame, Grid, Image, Margin, RichText, Stroke, TextureOptions, Ui, Vec2};
+
+use crate::{
+    backend::{
+        locales::TextCategory,
+        ressources_feature::{Resource, RessourcesSubsystem},
+    },
+    frontend::style,
+    tr,
+};
+
+const CARD_SPRITE_SIZE: f32 = 64.;
+
+// shared look of all data cards
+fn card_frame(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)) {
+    Frame::new()
+        .fill(Color32::from_black_alpha(80))
+        .stroke(Stroke::new(0.5, Color32::from_white_alpha(60)))
+        .corner_radius(8.)
+        .inner_margin(Margin::same(8))
+        .outer_margin(Margin::symmetric(0, 4))
+        .show(ui, add_contents);
+}
+
+// the data file doesn't know the name -> tell the guide author instead of rendering nothing
+fn unknown_data_card(ui: &mut Ui, message: String) {
+    card_frame(ui, |ui| {
+        ui.label(RichText::new(message).color(Color32::YELLOW));
+    });
+}
+
+fn card_sprite(ui: &mut Ui, sprite_uri: Option<String>) {
+    if let Some(uri) = sprite_uri {
+        // pixel art, so no blurry upscaling
+        ui.add(
+            Image::new(uri)
+                .texture_options(TextureOptions::NEAREST)
+                .fit_to_exact_size(Vec2::splat(CARD_SPRITE_SIZE)),
+        );
+    }
+}
+
+fn card_title(ui: &mut Ui, text: impl Into<String>) {
+    ui.label(RichText::new(text).heading().color(style::COLOR_HEADING_2));
+}
+
+////////////////////////////////////////
+/// Pokemon Card
+////////////////////////////////////////
+pub struct PokemonCardResource {
+    resource_key: String,
+    monster_name: String,
+}
+
+impl PokemonCardResource {
+    pub fn new(key: impl Into<String>, monster_name: impl Into<String>) -> Self {
+        Self {
+            resource_key: key.into(),
+            monster_name: monster_name.into(),
+        }
+    }
+}
+
+impl Resource for PokemonCardResource {
+    fn get_title(&self) -> String {
+        self.resource_key.clone()
+    }
+
+    fn render_resource(
+        &self,
+        resource_subsystem: &RessourcesSubsystem,
+        ui: &mut Ui,
+    ) -> Option<String> {
+        let game_data = resource_subsystem.get_game_data();
+        let Some(monster) = game_data.get_monster(&self.monster_name) else {
+            unknown_data_card(ui, tr!("⚠ unknown Pokemon \"{}\"", self.monster_name));
+            return None;
+        };
+
+        card_frame(ui, |ui| {
+            ui.horizontal(|ui| {
+                card_sprite(ui, game_data.get_sprite_uri("pokemon", monster.id));
+                ui.vertical(|ui| {
+                    card_title(
+                        ui,
+                        resource_subsystem.localize_id(
+                            TextCategory::Monster,
+                            monster.id,
+                            &monster.name,
+                        ),
+                    );
+                    ui.label(format!("#{:03}", monster.id));
+                    if !monster.types.is_empty() {
+                        let types: Vec<String> = monster
+                            .types
+                            .iter()
+                            .map(|t| resource_subsystem.localize(t))
+                            .collect();
+                        ui.label(types.join(" / "));
+                    }
+                });
+            });
+        });
+        None
+    }
+}
+
+////////////////////////////////////////
+/// Item Card
+////////////////////////////////////////
+pub struct ItemCardResource {
+    resource_key: String,
+    item_name: String,
+}
+
+impl ItemCardResource {
+    pub fn new(key: impl Into<String>, item_name: impl Into<String>) -> Self {
+        Self {
+            resource_key: key.into(),
+            item_name: item_name.into(),
+        }
+    }
+}
+
+impl Resource for ItemCardResource {
+    fn get_title(&self) -> String {
+        self.resource_key.clone()
+    }
+
+    fn render_resource(
+        &self,
+        resource_subsystem: &RessourcesSubsystem,
+        ui: &mut Ui,
+    ) -> Option<String> {
+        let game_data = resource_subsystem.get_game_data();
+        let Some(item) = game_data.get_item(&self.item_name) else {
+            unknown_data_card(ui, tr!("⚠ unknown Item \"{}\"", self.item_name));
+            return None;
+        };
+
+        card_frame(ui, |ui| {
+            ui.horizontal(|ui| {
+                card_sprite(ui, game_data.get_sprite_uri("items", item.icon_id));
+                ui.vertical(|ui| {
+                    card_title(
+                        ui,
+                        resource_subsystem.localize_id(TextCategory::Item, item.id, &item.name),
+                    );
+                    // descriptions contain the line breaks of the in-game text box
+                    let description = resource_subsystem
+                        .localize_id(TextCategory::ItemDescription, item.id, &item.desc)
+                        .replace('\n', " ");
+                    ui.label(description);
+                });
+            });
+        });
+        None
+    }
+}
+
+////////////////////////////////////////
+/// Move Card
+////////////////////////////////////////
+pub struct MoveCardResource {
+    resource_key: String,
+    move_name: String,
+}
+
+impl MoveCardResource {
+    pub fn new(key: impl Into<String>, move_name: impl Into<String>) -> Self {
+        Self {
+            resource_key: key.into(),
+            move_name: move_name.into(),
+        }
+    }
+}
+
+impl Resource for MoveCardResource {
+    fn get_title(&self) -> String {
+        self.resource_key.clone()
+    }
+
+    fn render_resource(
+        &self,
+        resource_subsystem: &RessourcesSubsystem,
+        ui: &mut Ui,
+    ) -> Option<String> {
+        let Some(move_data) = resource_subsystem.get_game_data().get_move(&self.move_name) else {
+            unknown_data_card(ui, tr!("⚠ unknown Move \"{}\"", self.move_name));
+            return None;
+        };
+
+        card_frame(ui, |ui| {
+            card_title(
+                ui,
+                resource_subsystem.localize_id(TextCategory::Move, move_data.id, &move_data.name),
+            );
+            ui.label(format!(
+                "{} · {}",
+                resource_subsystem.localize(&move_data.move_type),
+                resource_subsystem.localize(&move_data.skill_damage_type)
+            ));
+
+            // 0 means "doesn't apply" in the data (status moves, never-missing moves)
+            let display_stat = |value: u32| match value {
+                0 => "—".to_owned(),
+                v => v.to_string(),
+            };
+            Grid::new(&self.resource_key)
+                .num_columns(4)
+                .spacing(Vec2::new(16., 2.))
+                .show(ui, |ui| {
+                    ui.label(tr!("Power"));
+                    ui.label(tr!("Accuracy"));
+                    ui.label(tr!("PP"));
+                    ui.label(tr!("Priority"));
+                    ui.end_row();
+                    ui.label(display_stat(move_data.base_power));
+                    ui.label(display_stat(move_data.base_accuracy));
+                    ui.label(move_data.base_pp.to_string());
+                    ui.label(format!("{:+}", move_data.priority));
+                    ui.end_row();
+                });
+        });
+        None
+    }
+}
+
+////////////////////////////////////////
+/// Location Card
+////////////////////////////////////////
+/// renders the encounter table of a location, optionally only for one region
+/// (a lot of route names exist in multiple regions)
+pub struct LocationCardResource {
+    resource_key: String,
+    location_name: String,
+    region: Option<String>,
+}
+
+impl LocationCardResource {
+    pub fn new(
+        key: impl Into<String>,
+        location_name: impl Into<String>,
+        region: Option<String>,
+    ) -> Self {
+        Self {
+            resource_key: key.into(),
+            location_name: location_name.into(),
+            region,
+        }
+    }
+}
+
+impl Resource for LocationCardResource {
+    fn get_title(&self) -> String {
+        self.resource_key.clone()
+    }
+
+    fn render_resource(
+        &self,
+        resource_subsystem: &RessourcesSubsystem,
+        ui: &mut Ui,
+    ) -> Option<String> {
+        let game_data = resource_subsystem.get_game_data();
+        let locations: Vec<_> = game_data
+            .get_locations(&self.location_name)
+            .iter()
+            .filter(|location| {
+                self.region
+                    .as_ref()
+                    .is_none_or(|r| r.eq_ignore_ascii_case(&location.region_base_name))
+            })
+            .collect();
+
+        if locations.is_empty() {
+            unknown_data_card(ui, tr!("⚠ unknown Location \"{}\"", self.location_name));
+            return None;
+        }
+
+        for (i, location) in locations.into_iter().enumerate() {
+            card_frame(ui, |ui| {
+                card_title(
+                    ui,
+                    format!(
+                        "{} ({})",
+                        resource_subsystem.localize(&location.name),
+                        resource_subsystem.localize(&location.region_base_name)
+                    ),
+                );
+
+                Grid::new(format!("{}_{i}", self.resource_key))
+                    .striped(true)
+                    .num_columns(5)
+                    .spacing(Vec2::new(12., 2.))
+                    .show(ui, |ui| {
+                        for header in ["Pokemon", "Method", "Level", "Rarity", "Time"] {
+                            ui.label(RichText::new(tr!(header)).strong());
+                        }
+                        ui.end_row();
+
+                        for encounter in &location.encounters {
+                            let monster = match game_data.get_monster_name(encounter.pokemon_id) {
+                                Some(name) => resource_subsystem.localize_id(
+                                    TextCategory::Monster,
+                                    encounter.pokemon_id,
+                                    name,
+                                ),
+                                None => format!("#{}", encounter.pokemon_id),
+                            };
+                            ui.label(monster);
+                            ui.label(resource_subsystem.localize(&encounter.encounter_type));
+                            match encounter.min_level == encounter.max_level {
+                                true => ui.label(encounter.min_level.to_string()),
+                                false => ui.label(format!(
+                                    "{}-{}",
+                                    encounter.min_level, encounter.max_level
+                                )),
+                            };
+                            ui.label(resource_subsystem.localize(&encounter.rarity));
+                            let times: Vec<String> = encounter
+                                .time_restrictions
+                                .iter()
+                                .map(|t| resource_subsystem.localize(t))
+                                .collect();
+                            ui.label(times.join(", "));
+                            ui.end_row();
+                        }
+                    });
+            });
+        }
+        None
+    }
+}
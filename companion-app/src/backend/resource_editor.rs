@@ -0,0 +1,329 @@
+// state & file handling of the in-app resource editor (edits the md-files in assets/resources)
+// the split view itself is drawn in frontend/gui/resource_editor.rs
+
+use egui_commonmark::CommonMarkCache;
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    backend::{
+        locales,
+        resource_parser::{self, ParsedPage},
+        ressources_feature::{
+            PATH_SEPARATOR, RessourcesSubsystem, collect_page_keys, resource_display_name,
+            split_locale_prefix,
+        },
+    },
+    utils::{read_in_all_markdown_files, write_file_atomic},
+};
+
+// characters windows doesn't allow in file names, plus the anchor separator of links
+const INVALID_PAGE_NAME_CHARS: [char; 9] = ['<', '>', ':', '"', '\\', '|', '?', '*', '#'];
+
+pub struct ResourceEditor {
+    pub active: bool,
+
+    editing_page: Option<String>,
+    pub text: String,
+    saved_text: String,
+
+    // every md-file in assets/resources, refreshed after each file action
+    pages: Vec<String>,
+    page_keys: HashSet<String>,
+    locale_keys: Vec<String>,
+
+    // result of the last file action, shown in the toolbar
+    pub status: Option<String>,
+
+    // inputs of the toolbar popups
+    pub new_page_name: String,
+    pub rename_target: String,
+    pub web_link_text: String,
+    pub web_link_url: String,
+
+    // where new tags get inserted, written back by the gui every frame
+    pub cursor_char_index: Option<usize>,
+
+    // the preview is only parsed again when the text changed
+    preview: Option<ParsedPage>,
+    preview_source: String,
+    preview_cache: CommonMarkCache,
+}
+
+impl ResourceEditor {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            editing_page: None,
+            text: String::new(),
+            saved_text: String::new(),
+            pages: Vec::new(),
+            page_keys: HashSet::new(),
+            locale_keys: Vec::new(),
+            status: None,
+            new_page_name: String::new(),
+            rename_target: String::new(),
+            web_link_text: String::new(),
+            web_link_url: String::new(),
+            cursor_char_index: None,
+            preview: None,
+            preview_source: String::new(),
+            preview_cache: CommonMarkCache::default(),
+        }
+    }
+
+    /// enters the editor mode, starting on `page` if it is backed by a md-file
+    pub fn activate(&mut self, page: Option<&String>) {
+        self.active = true;
+        self.refresh_pages();
+
+        if let Some(page) = page
+            && self.editing_page.as_ref() != Some(page)
+            && !self.has_unsaved_changes()
+            && self.pages.contains(page)
+        {
+            self.open_page(page);
+        }
+    }
+
+    pub fn refresh_pages(&mut self) {
+        self.locale_keys = locales::read_locale_keys_from_disk();
+        self.pages = match RessourcesSubsystem::resource_folder_path()
+            .and_then(read_in_all_markdown_files)
+        {
+            Ok(files) => files.into_iter().map(|(key, _)| key).collect(),
+            Err(e) => {
+                self.status = Some(format!("couldn't read resources: {e}"));
+                Vec::new()
+            }
+        };
+        self.pages.sort_by_key(|key| key.to_lowercase());
+        self.page_keys = collect_page_keys(self.pages.iter().map(|key| {
+            split_locale_prefix(key, &self.locale_keys)
+                .map(|(_, page)| page)
+                .unwrap_or(key)
+        }));
+    }
+
+    pub fn get_pages(&self) -> &[String] {
+        &self.pages
+    }
+
+    /// every page and folder an AppLink can point to
+    pub fn get_page_keys(&self) -> &HashSet<String> {
+        &self.page_keys
+    }
+
+    pub fn get_editing_page(&self) -> Option<&String> {
+        self.editing_page.as_ref()
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.editing_page.is_some() && self.text != self.saved_text
+    }
+
+    /// drops unsaved changes (if any) and loads the page from disk
+    pub fn open_page(&mut self, key: &str) {
+        let result = RessourcesSubsystem::resource_folder_path()
+            .and_then(|dir| fs::read_to_string(page_file_path(&dir, key)));
+        match result {
+            Ok(contents) => {
+                self.editing_page = Some(key.to_owned());
+                self.text = contents.clone();
+                self.saved_text = contents;
+                self.cursor_char_index = None;
+                self.status = None;
+            }
+            Err(e) => self.status = Some(format!("couldn't open \"{key}\": {e}")),
+        }
+    }
+
+    pub fn save(&mut self) -> io::Result<()> {
+        let Some(key) = self.editing_page.clone() else {
+            return Ok(());
+        };
+        let dir = RessourcesSubsystem::resource_folder_path()?;
+        write_file_atomic(&page_file_path(&dir, &key), &self.text)?;
+        self.saved_text = self.text.clone();
+        self.status = Some(format!("saved \"{key}\""));
+        Ok(())
+    }
+
+    /// creates a new md-file with a heading and opens it, returns its key
+    pub fn create_page(&mut self, name: &str) -> io::Result<String> {
+        let key = validate_page_key(name)?;
+        let path = page_file_path(&RessourcesSubsystem::resource_folder_path()?, &key);
+        if path.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("\"{key}\" already exists"),
+            ));
+        }
+
+        write_file_atomic(&path, &format!("# {}\n", resource_display_name(&key)))?;
+        self.refresh_pages();
+        self.open_page(&key);
+        self.status = Some(format!("created \"{key}\""));
+        Ok(key)
+    }
+
+    /// moves the current page to a new name (or folder), unsaved changes are kept
+    pub fn rename_page(&mut self, new_name: &str) -> io::Result<String> {
+        let Some(old_key) = self.editing_page.clone() else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "no page opened"));
+        };
+        let new_key = validate_page_key(new_name)?;
+        let dir = RessourcesSubsystem::resource_folder_path()?;
+        let new_path = page_file_path(&dir, &new_key);
+        // only the case changed -> the file "exists" on case-insensitive file systems
+        if new_path.exists() && !new_key.eq_ignore_ascii_case(&old_key) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("\"{new_key}\" already exists"),
+            ));
+        }
+
+        if let Some(parent) = new_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(page_file_path(&dir, &old_key), &new_path)?;
+
+        self.editing_page = Some(new_key.clone());
+        self.refresh_pages();
+        self.status = Some(format!("renamed \"{old_key}\" to \"{new_key}\""));
+        Ok(new_key)
+    }
+
+    pub fn delete_page(&mut self) -> io::Result<()> {
+        let Some(key) = self.editing_page.clone() else {
+            return Ok(());
+        };
+        fs::remove_file(page_file_path(
+            &RessourcesSubsystem::resource_folder_path()?,
+            &key,
+        ))?;
+
+        self.editing_page = None;
+        self.text.clear();
+        self.saved_text.clear();
+        self.refresh_pages();
+        self.status = Some(format!("deleted \"{key}\""));
+        Ok(())
+    }
+
+    /// adds a tag on its own line below the cursor (tags have to be on a line of their own)
+    pub fn insert_tag(&mut self, tag: &str) {
+        let index = self
+            .cursor_char_index
+            .unwrap_or_else(|| self.text.chars().count());
+        self.cursor_char_index = Some(insert_tag_line(&mut self.text, index, tag));
+    }
+
+    /// the current text parsed like the resources window would, plus the cache to render it with
+    pub fn get_preview(&mut self) -> Option<(&ParsedPage, &mut CommonMarkCache)> {
+        let key = self.editing_page.as_ref()?;
+        if self.preview.is_none() || self.preview_source != self.text {
+            let link_base = split_locale_prefix(key, &self.locale_keys)
+                .map(|(_, page)| page)
+                .unwrap_or(key);
+            self.preview = Some(resource_parser::parse_page(
+                key,
+                link_base,
+                &self.text,
+                &self.page_keys,
+            ));
+            self.preview_source = self.text.clone();
+        }
+        Some((self.preview.as_ref()?, &mut self.preview_cache))
+    }
+}
+
+/// assets/resources/Guides/Ho-Oh-Guide.md for the key "Guides/Ho-Oh-Guide"
+pub fn page_file_path(resources_dir: &Path, key: &str) -> PathBuf {
+    let (folder, name) = key.rsplit_once(PATH_SEPARATOR).unwrap_or(("", key));
+    let mut path = resources_dir.to_path_buf();
+    path.extend(folder.split(PATH_SEPARATOR).filter(|f| !f.is_empty()));
+    path.join(format!("{name}.md")) // not with_extension, names may contain dots
+}
+
+/// cleans up what the user typed into a page key or explains why it can't be one
+pub fn validate_page_key(name: &str) -> io::Result<String> {
+    let invalid = |reason: String| io::Error::new(io::ErrorKind::InvalidInput, reason);
+
+    let name = name.trim().trim_matches(PATH_SEPARATOR);
+    let name = name.strip_suffix(".md").unwrap_or(name);
+    if name.is_empty() {
+        return Err(invalid("the page needs a name".to_owned()));
+    }
+    if let Some(c) = name.chars().find(|c| INVALID_PAGE_NAME_CHARS.contains(c)) {
+        return Err(invalid(format!("page names can't contain '{c}'")));
+    }
+    if name.starts_with("inner_") {
+        return Err(invalid(
+            "page names starting with \"inner_\" are reserved".to_owned(),
+        ));
+    }
+
+    let segments: Vec<&str> = name.split(PATH_SEPARATOR).map(str::trim).collect();
+    if segments
+        .iter()
+        .any(|segment| segment.is_empty() || segment.starts_with('.'))
+    {
+        return Err(invalid(format!("\"{name}\" isn't a valid path")));
+    }
+    Ok(segments.join(&PATH_SEPARATOR.to_string()))
+}
+
+/// inserts `tag` as a new line after the line `char_index` is in,
+/// returns the char index right behind the inserted tag
+pub fn insert_tag_line(text: &mut String, char_index: usize, tag: &str) -> usize {
+    let byte_index = text
+        .char_indices()
+        .nth(char_index)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let line_end = text[byte_index..]
+        .find('\n')
+        .map(|i| byte_index + i)
+        .unwrap_or(text.len());
+
+    let insertion = match text.is_empty() {
+        true => tag.to_owned(),
+        false => format!("\n{tag}"),
+    };
+    text.insert_str(line_end, &insertion);
+    text[..line_end + insertion.len()].chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_page_key() {
+        assert_eq!(
+            validate_page_key(" /Guides/ Ho-Oh.md ").unwrap(),
+            "Guides/Ho-Oh"
+        );
+        assert!(validate_page_key("").is_err());
+        assert!(validate_page_key("Guides/../ROOT").is_err());
+        assert!(validate_page_key("Guides//ROOT").is_err());
+        assert!(validate_page_key("Ho-Oh#notes").is_err());
+        assert!(validate_page_key("inner_ROOT_md_0").is_err());
+    }
+
+    #[test]
+    fn test_insert_tag_line() {
+        let mut text = "first\nsecond".to_owned();
+        let cursor = insert_tag_line(&mut text, 2, "<AppLink=A, B>");
+        assert_eq!(text, "first\n<AppLink=A, B>\nsecond");
+        assert_eq!(cursor, "first\n<AppLink=A, B>".len());
+
+        let mut text = String::new();
+        insert_tag_line(&mut text, 0, "<WebLink=A, B>");
+        assert_eq!(text, "<WebLink=A, B>");
+    }
+}
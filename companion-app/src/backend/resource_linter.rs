@@ -0,0 +1,393 @@
+// checks a resources folder for problems guide authors would otherwise only notice by clicking
+// through the app. run with: PokeMMO-Companion --check-resources [dir] [--check-web-links]
+// prints a json report to stdout and exits with 1 when any issue was found (2 if dir is unreadable)
+
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    path::PathBuf,
+    time::Duration,
+};
+
+use crate::{
+    backend::{
+        locales,
+        resource_packs::PACK_KEY_PREFIX,
+        resource_parser::{PageElement, expand_includes, parse_resource_files},
+        ressources_feature::{
+            INDEX_RESOURCE, PATH_SEPARATOR, ROOT_RESOURCE, parent_folder, split_anchor,
+        },
+    },
+    utils::{find_asset_folder, read_in_all_markdown_files},
+};
+
+const WEB_LINK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    DeadAppLink,
+    DeadAnchor,
+    UnreachablePage,
+    DuplicateKey,
+    MalformedTag,
+    BrokenInclude,
+    UnreachableWebLink,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LintIssue {
+    pub kind: LintKind,
+    pub page: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LintReport {
+    pub resources_dir: String,
+    pub pages_checked: usize,
+    pub issue_count: usize,
+    pub issues: Vec<LintIssue>,
+}
+
+/// entry point of the --check-resources mode, returns the process exit code
+pub fn run_check_resources(dir: Option<PathBuf>, check_web_links: bool) -> i32 {
+    let dir = match dir
+        .map(Ok)
+        .unwrap_or_else(|| find_asset_folder().map(|a| a.join("resources")))
+    {
+        Ok(dir) => dir,
+        Err(e) => {
+            println!("{}", serde_json::json!({ "error": e.to_string() }));
+            return 2;
+        }
+    };
+    let md_file_list = match read_in_all_markdown_files(dir.clone()) {
+        Ok(list) => list,
+        Err(e) => {
+            println!(
+                "{}",
+                serde_json::json!({ "error": format!("{}: {e}", dir.display()) })
+            );
+            return 2;
+        }
+    };
+
+    let mut issues = lint_resources(&md_file_list, &locales::read_locale_keys_from_disk());
+
+    if check_web_links {
+        match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => {
+                issues.extend(runtime.block_on(find_unreachable_web_links(&md_file_list)))
+            }
+            Err(e) => eprintln!("ResourceLinter - couldn't check web links: {e}"),
+        }
+    }
+
+    let report = LintReport {
+        resources_dir: dir.to_string_lossy().into_owned(),
+        pages_checked: md_file_list.len(),
+        issue_count: issues.len(),
+        issues,
+    };
+    // serializing plain structs can't fail
+    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+
+    match report.issue_count {
+        0 => 0,
+        _ => 1,
+    }
+}
+
+/// every check that doesn't need the network
+pub fn lint_resources(md_file_list: &[(String, String)], locale_keys: &[String]) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let parsed = parse_resource_files(md_file_list, locale_keys);
+
+    // DUPLICATE KEYS: case-insensitive file systems (Windows) would mix these up
+    // and generated inner resource keys must not be used as page names
+    let mut seen_keys: HashMap<String, &String> = HashMap::new();
+    for (key, _) in md_file_list {
+        if let Some(first) = seen_keys.insert(key.to_lowercase(), key) {
+            issues.push(LintIssue {
+                kind: LintKind::DuplicateKey,
+                page: key.clone(),
+                line: None,
+                message: format!("page key collides with \"{first}\""),
+            });
+        }
+        if key.starts_with("inner_") {
+            issues.push(LintIssue {
+                kind: LintKind::DuplicateKey,
+                page: key.clone(),
+                line: None,
+                message: "page names starting with \"inner_\" collide with generated resources"
+                    .to_owned(),
+            });
+        }
+    }
+
+    // MALFORMED TAGS
+    for page in &parsed.pages {
+        for issue in &page.issues {
+            issues.push(LintIssue {
+                kind: LintKind::MalformedTag,
+                page: page.key.clone(),
+                line: Some(issue.line),
+                message: issue.message.clone(),
+            });
+        }
+    }
+
+    // BROKEN INCLUDES (missing pages/sections, cycles, too deep)
+    for (page, issue) in expand_includes(&mut parsed.clone(), locale_keys) {
+        issues.push(LintIssue {
+            kind: LintKind::BrokenInclude,
+            page,
+            line: Some(issue.line),
+            message: issue.message,
+        });
+    }
+
+    // DEAD APP LINKS & ANCHORS
+    let mut known_pages = parsed.page_keys.clone();
+    known_pages.insert(PACK_KEY_PREFIX.to_owned()); // always generated by the app
+    known_pages.insert(INDEX_RESOURCE.to_owned());
+    // tag & region pages of the index depend on the front matter, all of them are fine to link
+    let index_prefix = format!("{INDEX_RESOURCE}{PATH_SEPARATOR}");
+    let md_pages: HashSet<&String> = parsed.pages.iter().map(|p| &p.link_base).collect();
+    let anchors: HashMap<&String, HashSet<&String>> = parsed
+        .pages
+        .iter()
+        .filter(|p| p.key == p.link_base) // anchors of the untranslated page count
+        .map(|p| {
+            (
+                &p.key,
+                p.table_of_contents.iter().map(|t| &t.anchor).collect(),
+            )
+        })
+        .collect();
+
+    // page -> pages it links to, for the reachability check below
+    let mut links: HashMap<String, Vec<String>> = HashMap::new();
+
+    for page in &parsed.pages {
+        for (line, element) in &page.elements {
+            // included pages are reachable through the page including them
+            if let PageElement::Include { target, .. } = element {
+                let (target_page, _) = split_anchor(target);
+                links
+                    .entry(page.link_base.clone())
+                    .or_default()
+                    .push(target_page.to_owned());
+            }
+            let PageElement::AppLink {
+                raw_target, target, ..
+            } = element
+            else {
+                continue;
+            };
+
+            let (target_page, anchor) = split_anchor(target);
+            let target_page = match target_page.is_empty() {
+                true => page.link_base.as_str(),
+                false => target_page,
+            };
+
+            if !known_pages.contains(target_page) && !target_page.starts_with(&index_prefix) {
+                issues.push(LintIssue {
+                    kind: LintKind::DeadAppLink,
+                    page: page.key.clone(),
+                    line: Some(*line),
+                    message: format!("AppLink target \"{raw_target}\" doesn't exist"),
+                });
+                continue;
+            }
+
+            if let Some(anchor) = anchor
+                && anchors
+                    .get(&target_page.to_owned())
+                    .is_some_and(|page_anchors| !page_anchors.contains(&anchor.to_owned()))
+            {
+                issues.push(LintIssue {
+                    kind: LintKind::DeadAnchor,
+                    page: page.key.clone(),
+                    line: Some(*line),
+                    message: format!("\"{target_page}\" has no heading with anchor #{anchor}"),
+                });
+            }
+
+            // links into the index (e.g. a tag page) are treated like links to the whole index
+            let target_page = match target_page.starts_with(&index_prefix) {
+                true => INDEX_RESOURCE,
+                false => target_page,
+            };
+            links
+                .entry(page.link_base.clone())
+                .or_default()
+                .push(target_page.to_owned());
+        }
+    }
+
+    // folders without a hand-written page get a generated category page linking to their content
+    for key in &known_pages {
+        let folder = parent_folder(key);
+        if !folder.is_empty() && !md_pages.contains(&folder.to_owned()) {
+            links
+                .entry(folder.to_owned())
+                .or_default()
+                .push(key.clone());
+        }
+    }
+
+    // the generated index lists every page that isn't hidden
+    links.entry(INDEX_RESOURCE.to_owned()).or_default().extend(
+        parsed
+            .pages
+            .iter()
+            .filter(|p| p.key == p.link_base && !p.front_matter.hidden)
+            .map(|p| p.key.clone()),
+    );
+
+    // UNREACHABLE PAGES (breadth-first from ROOT)
+    let mut reachable: HashSet<String> = HashSet::new();
+    let mut queue = VecDeque::from([ROOT_RESOURCE.to_owned()]);
+    while let Some(key) = queue.pop_front() {
+        if !reachable.insert(key.clone()) {
+            continue;
+        }
+        if let Some(targets) = links.get(&key) {
+            queue.extend(targets.iter().cloned());
+        }
+    }
+
+    if !md_pages.contains(&ROOT_RESOURCE.to_owned()) {
+        issues.push(LintIssue {
+            kind: LintKind::UnreachablePage,
+            page: ROOT_RESOURCE.to_owned(),
+            line: None,
+            message: "there is no ROOT.md, the app starts on it".to_owned(),
+        });
+    }
+
+    let mut unreachable: Vec<&String> = parsed
+        .pages
+        .iter()
+        .filter(|p| p.key == p.link_base && !reachable.contains(&p.key))
+        .map(|p| &p.key)
+        .collect();
+    unreachable.sort();
+    for key in unreachable {
+        issues.push(LintIssue {
+            kind: LintKind::UnreachablePage,
+            page: key.clone(),
+            line: None,
+            message: "page isn't linked from ROOT (directly or through other pages)".to_owned(),
+        });
+    }
+
+    issues
+}
+
+/// tries every distinct WebLink url once, only runs when asked for (--check-web-links), so it
+/// doesn't go through the network policy of the app's settings
+pub async fn find_unreachable_web_links(md_file_list: &[(String, String)]) -> Vec<LintIssue> {
+    let parsed = parse_resource_files(md_file_list, &[]);
+    let client = match reqwest::Client::builder().timeout(WEB_LINK_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("ResourceLinter - couldn't create http client: {e}");
+            return Vec::new();
+        }
+    };
+
+    let mut url_results: HashMap<String, Option<String>> = HashMap::new();
+    let mut issues = Vec::new();
+    for page in &parsed.pages {
+        for (line, element) in &page.elements {
+            let PageElement::WebLink { url, .. } = element else {
+                continue;
+            };
+
+            if !url_results.contains_key(url) {
+                let result = check_web_link(&client, url).await.err();
+                url_results.insert(url.clone(), result);
+            }
+
+            if let Some(Some(error)) = url_results.get(url) {
+                issues.push(LintIssue {
+                    kind: LintKind::UnreachableWebLink,
+                    page: page.key.clone(),
+                    line: Some(*line),
+                    message: format!("{url} : {error}"),
+                });
+            }
+        }
+    }
+    issues
+}
+
+// a lot of servers don't answer HEAD requests properly, so GET is the fallback
+async fn check_web_link(client: &reqwest::Client, url: &str) -> Result<(), String> {
+    if let Ok(response) = client.head(url).send().await
+        && response.status().is_success()
+    {
+        return Ok(());
+    }
+    match client.get(url).send().await {
+        Ok(response) if response.status().is_success() => Ok(()),
+        Ok(response) => Err(format!("status {}", response.status())),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds_for(issues: &[LintIssue], page: &str) -> Vec<LintKind> {
+        issues
+            .iter()
+            .filter(|i| i.page == page)
+            .map(|i| i.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_resources() {
+        let files: Vec<(String, String)> = [
+            ("ROOT", "<AppLink=Guide, Guides/Ho-Oh#moves>\n<AppLink=Nope, Missing>\n<WebLink=x\n<AppLink=All, Guides>\n<Include=Snippet>\n<Include=Missing>"),
+            ("Snippet", "only included, never linked"),
+            ("Guides/Ho-Oh", "# Team\n<AppLink=Back, /ROOT>"),
+            ("Guides/Lugia", "reachable through the generated Guides category"),
+            ("Orphan", "nobody links here"),
+            ("orphan", "same key on windows"),
+        ]
+        .into_iter()
+        .map(|(k, c)| (k.to_owned(), c.to_owned()))
+        .collect();
+
+        let issues = lint_resources(&files, &[]);
+        assert_eq!(
+            kinds_for(&issues, "ROOT"),
+            [
+                LintKind::MalformedTag,
+                LintKind::BrokenInclude,
+                LintKind::DeadAnchor,
+                LintKind::DeadAppLink
+            ]
+        );
+        assert!(kinds_for(&issues, "Guides/Ho-Oh").is_empty());
+        assert!(kinds_for(&issues, "Guides/Lugia").is_empty());
+        assert!(kinds_for(&issues, "Snippet").is_empty());
+        assert_eq!(kinds_for(&issues, "Orphan"), [LintKind::UnreachablePage]);
+        assert_eq!(
+            kinds_for(&issues, "orphan"),
+            [LintKind::DuplicateKey, LintKind::UnreachablePage]
+        );
+    }
+}
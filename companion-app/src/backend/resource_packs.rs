@@ -0,0 +1,472 @@
+// community resource packs: versioned bundles of md-files, downloaded from a configurable base url
+// remote layout:
+//   <base_url>/index.json              -> ["pack-id", ...]
+//   <base_url>/<pack-id>/manifest.json -> ResourcePackManifest
+//   <base_url>/<pack-id>/<file>        -> every file listed in the manifest (size & sha256 checked)
+// packs install into assets/resource_packs/<pack-id>/ and are loaded as "packs/<pack-id>/...",
+// so they never touch (or collide with) the user's own resources
+
+use super::{async_manager::AsyncManager, locale_manifest::ManifestEntry, network::NetworkAccess};
+use crate::utils::{download_to_path_verified, fetch_text, find_asset_folder};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{Arc, RwLock},
+};
+
+pub const PACK_INDEX_FILE: &str = "index.json";
+pub const PACK_MANIFEST_FILE: &str = "manifest.json";
+/// resource key prefix of everything inside a pack, "packs" is also the category page listing them
+pub const PACK_KEY_PREFIX: &str = "packs";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourcePackManifest {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub author: String,
+    /// path relative to the pack folder (e.g. "Guides/Ho-Oh-Guide.md") -> size & sha256
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Default)]
+pub struct ResourcePackData {
+    pub installed: Vec<ResourcePackManifest>,
+    pub available: Vec<ResourcePackManifest>, // filled by the last update check
+    pub last_error: Option<String>,
+}
+
+pub struct ResourcePackSubsystem {
+    pub data: Arc<RwLock<ResourcePackData>>,
+    pub install_counter: Arc<RwLock<usize>>, // incremented whenever installed packs change on disk
+
+    async_manager: Rc<AsyncManager>,
+    network: NetworkAccess,
+}
+
+impl ResourcePackSubsystem {
+    pub fn new(async_manager: Rc<AsyncManager>, network: NetworkAccess) -> Self {
+        let subsystem = Self {
+            data: Arc::new(RwLock::new(ResourcePackData::default())),
+            install_counter: Arc::new(RwLock::new(0)),
+            async_manager,
+            network,
+        };
+
+        subsystem.reload_installed_packs();
+
+        subsystem
+    }
+
+    pub fn reload_installed_packs(&self) {
+        let installed = match get_resource_packs_dir() {
+            Ok(dir) => read_installed_packs(&dir),
+            Err(_) => Vec::new(),
+        };
+        self.data.write().unwrap().installed = installed;
+    }
+
+    pub fn trigger_update_check(&self, base_url: &str) {
+        println!("ResourcePackSubsystem - checking {base_url} for resource packs ...");
+
+        let base_url = base_url.to_owned();
+        let data_ref = self.data.clone();
+        let network = self.network.clone();
+        self.async_manager
+            .spawn_unique("ResourcePackSubsystem_Check", async move {
+                let result = fetch_available_packs(&network, &base_url).await;
+                let mut data = data_ref.write().unwrap();
+                match result {
+                    Ok(available) => {
+                        data.available = available;
+                        data.last_error = None;
+                    }
+                    Err(e) => {
+                        eprintln!("ResourcePackSubsystem - update check failed, because {e}");
+                        data.last_error = Some(format!("update check failed: {e}"));
+                    }
+                }
+            });
+    }
+
+    /// installs a new pack or updates an installed one
+    pub fn trigger_install(&self, base_url: &str, pack_id: &str) {
+        println!("ResourcePackSubsystem - installing resource pack {pack_id} ...");
+
+        let base_url = base_url.to_owned();
+        let pack_id = pack_id.to_owned();
+        let data_ref = self.data.clone();
+        let counter_ref = self.install_counter.clone();
+        let network = self.network.clone();
+        self.async_manager.spawn_unique(
+            format!("ResourcePackSubsystem_Install_{pack_id}"),
+            async move {
+                let result = match get_resource_packs_dir() {
+                    Ok(packs_dir) => install_pack(&network, &base_url, &pack_id, &packs_dir).await,
+                    Err(e) => Err(e.into()),
+                };
+
+                let mut data = data_ref.write().unwrap();
+                match result {
+                    Ok(manifest) => {
+                        println!(
+                            "ResourcePackSubsystem - installed {} v{}",
+                            manifest.name, manifest.version
+                        );
+                        data.installed.retain(|p| p.id != manifest.id);
+                        data.installed.push(manifest);
+                        data.last_error = None;
+                        *counter_ref.write().unwrap() += 1;
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "ResourcePackSubsystem - couldn't install {pack_id}, because {e}"
+                        );
+                        data.last_error = Some(format!("couldn't install {pack_id}: {e}"));
+                    }
+                }
+            },
+        );
+    }
+
+    pub fn uninstall(&self, pack_id: &str) {
+        let result = get_resource_packs_dir()
+            .and_then(|dir| validate_pack_id(pack_id).map(|_| dir.join(pack_id)))
+            .and_then(fs::remove_dir_all);
+
+        let mut data = self.data.write().unwrap();
+        match result {
+            Ok(()) => {
+                data.installed.retain(|p| p.id != pack_id);
+                *self.install_counter.write().unwrap() += 1;
+            }
+            Err(e) => {
+                eprintln!("ResourcePackSubsystem - couldn't uninstall {pack_id}, because {e}");
+                data.last_error = Some(format!("couldn't uninstall {pack_id}: {e}"));
+            }
+        }
+    }
+
+    // GETTERS ///////////////////////////////////////////////////////////
+    pub fn get_install_counter(&self) -> usize {
+        *self.install_counter.read().unwrap()
+    }
+
+    pub fn get_installed_packs(&self) -> Vec<ResourcePackManifest> {
+        self.data.read().unwrap().installed.clone()
+    }
+
+    /// packs from the last update check, which are not installed in this version yet
+    pub fn get_installable_packs(&self) -> Vec<ResourcePackManifest> {
+        let data = self.data.read().unwrap();
+        data.available
+            .iter()
+            .filter(|available| {
+                data.installed
+                    .iter()
+                    .find(|installed| installed.id == available.id)
+                    .is_none_or(|installed| {
+                        is_newer_version(&available.version, &installed.version)
+                    })
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_installed(&self, pack_id: &str) -> bool {
+        let data = self.data.read().unwrap();
+        data.installed.iter().any(|p| p.id == pack_id)
+    }
+
+    pub fn get_last_error(&self) -> Option<String> {
+        self.data.read().unwrap().last_error.clone()
+    }
+}
+
+pub fn get_resource_packs_dir() -> io::Result<PathBuf> {
+    Ok(find_asset_folder()?.join("resource_packs"))
+}
+
+/// every (valid) installed pack in the given folder, broken packs are skipped
+pub fn read_installed_packs(packs_dir: &Path) -> Vec<ResourcePackManifest> {
+    let Ok(read_dir) = fs::read_dir(packs_dir) else {
+        return Vec::new(); // no packs installed yet
+    };
+
+    let mut installed = Vec::new();
+    for entry in read_dir.flatten() {
+        // skip staging folders of installs in progress
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let manifest_path = entry.path().join(PACK_MANIFEST_FILE);
+        let manifest = fs::read_to_string(&manifest_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| Ok(serde_json::from_str::<ResourcePackManifest>(&content)?));
+        match manifest {
+            Ok(manifest) => installed.push(manifest),
+            Err(e) => eprintln!("ResourcePackSubsystem - skipping pack {manifest_path:?}: {e}"),
+        }
+    }
+    installed.sort_by(|a, b| a.name.cmp(&b.name));
+    installed
+}
+
+pub async fn fetch_available_packs(
+    network: &NetworkAccess,
+    base_url: &str,
+) -> anyhow::Result<Vec<ResourcePackManifest>> {
+    let index: Vec<String> =
+        serde_json::from_str(&fetch_text(network, &pack_url(base_url, PACK_INDEX_FILE)).await?)?;
+
+    let mut available = Vec::new();
+    for pack_id in index {
+        match fetch_manifest(network, base_url, &pack_id).await {
+            Ok(manifest) => available.push(manifest),
+            // one broken pack shouldn't hide all the others
+            Err(e) => eprintln!("ResourcePackSubsystem - skipping remote pack {pack_id}: {e}"),
+        }
+    }
+    Ok(available)
+}
+
+async fn fetch_manifest(
+    network: &NetworkAccess,
+    base_url: &str,
+    pack_id: &str,
+) -> anyhow::Result<ResourcePackManifest> {
+    validate_pack_id(pack_id)?;
+    let manifest_url = pack_url(base_url, &format!("{pack_id}/{PACK_MANIFEST_FILE}"));
+    let manifest: ResourcePackManifest =
+        serde_json::from_str(&fetch_text(network, &manifest_url).await?)?;
+    if manifest.id != pack_id {
+        anyhow::bail!("manifest id \"{}\" doesn't match pack id", manifest.id);
+    }
+    for file in manifest.files.keys() {
+        validate_pack_file(file)?;
+    }
+    Ok(manifest)
+}
+
+/// downloads the whole pack into a staging folder first and only replaces the installed
+/// version once every file arrived, so a failed update never leaves a half-installed pack
+pub async fn install_pack(
+    network: &NetworkAccess,
+    base_url: &str,
+    pack_id: &str,
+    packs_dir: &Path,
+) -> anyhow::Result<ResourcePackManifest> {
+    let manifest = fetch_manifest(network, base_url, pack_id).await?;
+
+    let staging_dir = packs_dir.join(format!(".{pack_id}.staging"));
+    let _ = tokio::fs::remove_dir_all(&staging_dir).await; // leftovers of an earlier failed install
+
+    let download_result = async {
+        for (file, entry) in &manifest.files {
+            let file_url = pack_url(base_url, &format!("{pack_id}/{file}"));
+            let dest = staging_dir.join(file);
+            download_to_path_verified(network, &file_url, dest, entry.size, &entry.sha256).await?;
+        }
+        tokio::fs::write(
+            staging_dir.join(PACK_MANIFEST_FILE),
+            serde_json::to_string_pretty(&manifest)?,
+        )
+        .await?;
+        anyhow::Ok(())
+    }
+    .await;
+
+    if let Err(e) = download_result {
+        let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+        return Err(e);
+    }
+
+    let install_dir = packs_dir.join(pack_id);
+    let backup_dir = packs_dir.join(format!(".{pack_id}.backup"));
+    let result = swap_in_pack(&staging_dir, &backup_dir, &install_dir);
+
+    let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+    let _ = tokio::fs::remove_dir_all(&backup_dir).await;
+    result.map(|_| manifest).map_err(anyhow::Error::from)
+}
+
+// moves the staged pack into place, the old version is kept as backup until that worked
+fn swap_in_pack(staging_dir: &Path, backup_dir: &Path, install_dir: &Path) -> io::Result<()> {
+    let _ = fs::remove_dir_all(backup_dir); // leftovers of an earlier failed install
+    let had_old_version = install_dir.exists();
+    if had_old_version {
+        fs::rename(install_dir, backup_dir)?;
+    }
+    if let Err(e) = fs::rename(staging_dir, install_dir) {
+        eprintln!("ResourcePackSubsystem - couldn't replace {install_dir:?} ({e}), rolling back");
+        if had_old_version && let Err(e) = fs::rename(backup_dir, install_dir) {
+            eprintln!("ResourcePackSubsystem - couldn't restore {install_dir:?}, because {e}");
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn pack_url(base_url: &str, path: &str) -> String {
+    format!("{}/{path}", base_url.trim_end_matches('/'))
+}
+
+// pack ids become folder names and resource keys
+fn validate_pack_id(pack_id: &str) -> io::Result<()> {
+    let valid = !pack_id.is_empty()
+        && pack_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    match valid {
+        true => Ok(()),
+        false => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid resource pack id \"{pack_id}\""),
+        )),
+    }
+}
+
+// files must stay inside their pack folder
+fn validate_pack_file(file: &str) -> io::Result<()> {
+    let path = Path::new(file);
+    let stays_inside = !file.is_empty()
+        && path
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)));
+    match stays_inside {
+        true => Ok(()),
+        false => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid resource pack file \"{file}\""),
+        )),
+    }
+}
+
+/// compares dot separated versions numerically ("1.10.0" > "1.9"), non-numeric parts count as 0
+pub fn is_newer_version(candidate: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .trim()
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    let (mut candidate, mut current) = (parse(candidate), parse(current));
+    let len = candidate.len().max(current.len());
+    candidate.resize(len, 0);
+    current.resize(len, 0);
+    candidate > current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::network::NetworkMode;
+    use crate::backend::test_utils::{serve, temp_dir};
+    use std::collections::HashMap;
+
+    const MANIFEST: &str = r#"{"id": "guides", "name": "Guides", "version": "1.2.0",
+        "author": "someone", "files": {
+            "Start.md": {"size": 7,
+                "sha256": "2307c1da190a0afa18620121e954ea73dcd9db2aac6729463bcb85fcaed61a06"},
+            "Legendaries/Ho-Oh.md": {"size": 7,
+                "sha256": "826b6251c717e1cd061eb4dbab48bd13f1b253de952a539f38fda7c4410727c8"}
+        }}"#;
+
+    #[tokio::test]
+    async fn test_install_pack_from_local_server() {
+        let (base_url, _) = serve(HashMap::from([
+            ("/index.json", r#"["guides", "missing"]"#),
+            ("/guides/manifest.json", MANIFEST),
+            ("/guides/Start.md", "# Start"),
+            ("/guides/Legendaries/Ho-Oh.md", "# Ho-Oh"),
+        ]));
+
+        let network = NetworkAccess::new(NetworkMode::Automatic);
+        let available = fetch_available_packs(&network, &base_url).await.unwrap();
+        assert_eq!(available.len(), 1); // "missing" has no manifest and is skipped
+        assert_eq!(available[0].version, "1.2.0");
+
+        let packs_dir = temp_dir("install");
+        let manifest = install_pack(&network, &base_url, "guides", &packs_dir)
+            .await
+            .unwrap();
+        assert_eq!(manifest, available[0]);
+        assert_eq!(
+            fs::read_to_string(packs_dir.join("guides/Legendaries/Ho-Oh.md")).unwrap(),
+            "# Ho-Oh"
+        );
+        assert_eq!(read_installed_packs(&packs_dir), available);
+
+        let _ = fs::remove_dir_all(&packs_dir);
+    }
+
+    #[tokio::test]
+    async fn test_failed_install_keeps_old_version() {
+        // manifest lists a file the server doesn't have, the other one doesn't match its sha256
+        let (base_url, _) = serve(HashMap::from([
+            ("/guides/manifest.json", MANIFEST),
+            ("/guides/Start.md", "# Start v2"),
+        ]));
+
+        let packs_dir = temp_dir("rollback");
+        fs::create_dir_all(packs_dir.join("guides")).unwrap();
+        fs::write(packs_dir.join("guides/Start.md"), "# Start v1").unwrap();
+
+        let network = NetworkAccess::new(NetworkMode::Automatic);
+        assert!(
+            install_pack(&network, &base_url, "guides", &packs_dir)
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            fs::read_to_string(packs_dir.join("guides/Start.md")).unwrap(),
+            "# Start v1"
+        );
+        assert!(!packs_dir.join(".guides.staging").exists());
+
+        let _ = fs::remove_dir_all(&packs_dir);
+    }
+
+    #[test]
+    fn test_failed_swap_restores_old_version() {
+        let packs_dir = temp_dir("swap");
+        fs::create_dir_all(packs_dir.join("guides")).unwrap();
+        fs::write(packs_dir.join("guides/Start.md"), "# Start v1").unwrap();
+
+        // the staging folder doesn't exist, so moving it into place fails
+        let result = swap_in_pack(
+            &packs_dir.join(".guides.staging"),
+            &packs_dir.join(".guides.backup"),
+            &packs_dir.join("guides"),
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            fs::read_to_string(packs_dir.join("guides/Start.md")).unwrap(),
+            "# Start v1"
+        );
+
+        let _ = fs::remove_dir_all(&packs_dir);
+    }
+
+    #[test]
+    fn test_validate_pack_paths() {
+        assert!(validate_pack_id("community-guides_2").is_ok());
+        assert!(validate_pack_id("../evil").is_err());
+        assert!(validate_pack_file("Guides/Ho-Oh.md").is_ok());
+        assert!(validate_pack_file("../ROOT.md").is_err());
+        assert!(validate_pack_file("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_is_newer_version() {
+        assert!(is_newer_version("1.10.0", "1.9"));
+        assert!(is_newer_version("v2", "1.9.9"));
+        assert!(!is_newer_version("1.0", "1.0.0"));
+        assert!(!is_newer_version("0.9", "1.0"));
+    }
+}
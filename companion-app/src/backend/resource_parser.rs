@@ -0,0 +1,676 @@
+// pure parsing of resource md-files into page elements, no egui and no subsystem state involved
+// used by the RessourcesSubsystem to build its resources and by the resource linter (--check-resources)
+
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+use crate::backend::{
+    resource_packs::PACK_KEY_PREFIX,
+    ressources_feature::{
+        ANCHOR_SEPARATOR, PATH_SEPARATOR, TocEntry, collect_page_keys, heading_anchor,
+        parent_folder, resolve_resource_link, split_anchor, split_locale_prefix, unique_anchor,
+    },
+};
+
+/// tag types with a meaning, any other `<Word=...>` is reported as malformed
+pub const KNOWN_TAG_TYPES: [&str; 8] = [
+    "AppLink", "WebLink", "Pokemon", "Item", "Move", "Location", "Table", "Include",
+];
+
+/// how deep <Include=...> tags may be nested (a page including a page including a page ...)
+pub const MAX_INCLUDE_DEPTH: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageElement {
+    Markdown(String),
+    Heading(TocEntry),
+    AppLink {
+        text: Option<String>, // None shows the title of the target page (<AppLink=Page>)
+        raw_target: String,   // as written in the file
+        target: String,       // resolved page key (+ anchor)
+    },
+    WebLink {
+        text: String,
+        url: String,
+    },
+    DataCard {
+        tag_type: String, // one of Pokemon, Item, Move, Location
+        name: String,
+        extra: Option<String>, // e.g. the region of a location
+    },
+    Table {
+        file: String, // csv/json file, relative to the folder of the md-file
+    },
+    Include {
+        raw_target: String, // as written in the file
+        target: String,     // resolved page key (+ anchor of the section to include)
+    },
+    Checkbox {
+        text: String,
+        id: String,    // stays the same as long as the text does, unique within the page
+        checked: bool, // "- [x]" in the file, what the checkbox starts as
+    },
+}
+
+/// a problem found while parsing, lines start at 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseIssue {
+    pub line: usize,
+    pub message: String,
+}
+
+/// optional metadata block at the very top of a md-file, e.g.
+/// ---
+/// title: Ho-Oh Guide
+/// tags: legendary, pve
+/// ---
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontMatter {
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    pub icon: Option<String>, // shown in front of the title, e.g. an emoji
+    pub order: Option<i32>,   // pages with an order come first in generated lists, lowest first
+    pub region: Option<String>,
+    pub hidden: bool, // left out of generated category & index pages
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedPage {
+    pub key: String,       // key of the md-file (e.g. "DE/Guides/Ho-Oh-Guide")
+    pub link_base: String, // page links are resolved from (translations link like their original)
+    pub front_matter: FrontMatter,
+    pub elements: Vec<(usize, PageElement)>, // with the line they start on
+    pub table_of_contents: Vec<TocEntry>,
+    pub issues: Vec<ParseIssue>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedResources {
+    pub pages: Vec<ParsedPage>,
+    /// every page and every folder (category) in its untranslated form
+    pub page_keys: HashSet<String>,
+}
+
+/// parses every (key, content) pair of md-files, resources/<LOCALE>/ files count as translations
+pub fn parse_resource_files(
+    md_file_list: &[(String, String)],
+    locale_keys: &[String],
+) -> ParsedResources {
+    // every page and every folder (category) is known up front, so links can be resolved
+    let page_keys = collect_page_keys(md_file_list.iter().map(|(key, _)| {
+        split_locale_prefix(key, locale_keys)
+            .map(|(_, page)| page)
+            .unwrap_or(key)
+    }));
+
+    let pages = md_file_list
+        .iter()
+        .map(|(key, contents)| {
+            // links in translated pages work exactly like the ones in the default page
+            let link_base = split_locale_prefix(key, locale_keys)
+                .map(|(_, page)| page)
+                .unwrap_or(key);
+            parse_page(key, link_base, contents, &page_keys)
+        })
+        .collect();
+
+    ParsedResources { pages, page_keys }
+}
+
+/// splits one md-file into markdown, headings and resource tags
+pub fn parse_page(
+    key: &str,
+    link_base: &str,
+    contents: &str,
+    page_keys: &HashSet<String>,
+) -> ParsedPage {
+    // regex building should never fail
+    let regex_search_for_res_tag = Regex::new(r"<([^=]+)=([^,>]+)(?:,([^,>]+))?>").unwrap();
+    let regex_search_for_heading = Regex::new(r"^(#{1,6})\s+(.+?)\s*#*\s*$").unwrap();
+    // anything that starts like a tag, used to find the broken ones
+    let regex_search_for_tag_start = Regex::new(r"<(\w+)=").unwrap();
+    let regex_search_for_checkbox = Regex::new(r"^\s*[-*+]\s+\[([ xX])\]\s+(.+?)\s*$").unwrap();
+    let mut checkbox_ids: Vec<String> = Vec::new();
+
+    let mut page = ParsedPage {
+        key: key.to_owned(),
+        link_base: link_base.to_owned(),
+        front_matter: FrontMatter::default(),
+        elements: Vec::new(),
+        table_of_contents: Vec::new(),
+        issues: Vec::new(),
+    };
+
+    let body_start = parse_front_matter(contents, &mut page);
+
+    let mut md_content = String::new();
+    let mut md_start_line = body_start + 1;
+    let mut in_code_block = false; // headings and tags inside fenced code blocks are just text
+
+    for (i, line) in contents.lines().enumerate().skip(body_start) {
+        let line_number = i + 1;
+        let mut element: Option<PageElement> = None;
+
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+
+        // check for headings, which become their own resource so they can be scrolled to
+        if !in_code_block && let Some(heading) = regex_search_for_heading.captures(line) {
+            let text = heading[2].to_owned();
+            let anchor = unique_anchor(&heading_anchor(&text), &page.table_of_contents);
+            let toc_entry = TocEntry {
+                level: heading[1].len(),
+                text,
+                anchor,
+            };
+            page.table_of_contents.push(toc_entry.clone());
+            element = Some(PageElement::Heading(toc_entry));
+        } else if !in_code_block && let Some(res_tag) = regex_search_for_res_tag.captures(line) {
+            let res_type: &str = res_tag[1].trim(); // are guaranteed to exist
+            let res_text: &str = res_tag[2].trim(); // otherwise would regex not match
+            let res_data: &str = res_tag.get(3).map(|m| m.as_str()).unwrap_or("").trim();
+
+            element = match res_type {
+                // without a text the link shows the title of the page it points to
+                "AppLink" if res_data.is_empty() => Some(PageElement::AppLink {
+                    text: None,
+                    raw_target: res_text.to_owned(),
+                    target: resolve_resource_link(link_base, res_text, page_keys),
+                }),
+                "WebLink" if res_data.is_empty() => {
+                    page.issues.push(ParseIssue {
+                        line: line_number,
+                        message: format!(
+                            "{res_type} \"{res_text}\" has no target, expected <{res_type}=text, target>"
+                        ),
+                    });
+                    None
+                }
+                "AppLink" => Some(PageElement::AppLink {
+                    text: Some(res_text.to_owned()),
+                    raw_target: res_data.to_owned(),
+                    target: resolve_resource_link(link_base, res_data, page_keys),
+                }),
+                "WebLink" => Some(PageElement::WebLink {
+                    text: res_text.to_owned(),
+                    url: res_data.to_owned(),
+                }),
+                "Table" => Some(PageElement::Table {
+                    file: res_text.to_owned(),
+                }),
+                "Include" => Some(PageElement::Include {
+                    raw_target: res_text.to_owned(),
+                    target: resolve_resource_link(link_base, res_text, page_keys),
+                }),
+                "Pokemon" | "Item" | "Move" | "Location" => Some(PageElement::DataCard {
+                    tag_type: res_type.to_owned(),
+                    name: res_text.to_owned(),
+                    // optional region, because route names repeat across regions
+                    extra: (!res_data.is_empty()).then(|| res_data.to_owned()),
+                }),
+                _ => {
+                    // html tags (e.g. <img src=...>) have spaces in front of the '=', ignore those
+                    if res_type.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                        page.issues.push(ParseIssue {
+                            line: line_number,
+                            message: format!(
+                                "unknown tag type \"{res_type}\", known are: {}",
+                                KNOWN_TAG_TYPES.join(", ")
+                            ),
+                        });
+                    }
+                    None
+                }
+            };
+        } else if !in_code_block && let Some(checkbox) = regex_search_for_checkbox.captures(line) {
+            let text = checkbox[2].to_owned();
+            let id = checklist_item_id(&text, &checkbox_ids);
+            checkbox_ids.push(id.clone());
+            element = Some(PageElement::Checkbox {
+                text,
+                id,
+                checked: &checkbox[1] != " ",
+            });
+        } else if !in_code_block && let Some(tag_start) = regex_search_for_tag_start.captures(line)
+        {
+            page.issues.push(ParseIssue {
+                line: line_number,
+                message: format!(
+                    "malformed <{}=...> tag, it is missing its '>'",
+                    &tag_start[1]
+                ),
+            });
+        }
+
+        if let Some(element) = element {
+            // markdown collected so far goes first
+            if !md_content.is_empty() {
+                page.elements.push((
+                    md_start_line,
+                    PageElement::Markdown(std::mem::take(&mut md_content)),
+                ));
+            }
+            page.elements.push((line_number, element));
+            md_start_line = line_number + 1;
+            continue;
+        }
+
+        // no resource tag => treat as regular markdown & collect
+        md_content.push_str(line); // just add to next markdown resource
+        md_content.push('\n'); // line does not include the original line break
+    }
+
+    // last element could be collected but not yet added markdown
+    if !md_content.is_empty() {
+        page.elements
+            .push((md_start_line, PageElement::Markdown(md_content)));
+    }
+
+    page
+}
+
+/// replaces every <Include=Page> / <Include=Page#heading> with the elements of that page (or
+/// section), translations include the translated page if there is one.
+/// returns what couldn't be included as (page key, issue), those includes become a warning text
+pub fn expand_includes(
+    resources: &mut ParsedResources,
+    locale_keys: &[String],
+) -> Vec<(String, ParseIssue)> {
+    // includes of included pages are expanded while inlining them, hence the unexpanded copies
+    let sources: HashMap<String, ParsedPage> = resources
+        .pages
+        .iter()
+        .map(|page| (page.key.clone(), page.clone()))
+        .collect();
+
+    let mut issues = Vec::new();
+    for page in &mut resources.pages {
+        if !page
+            .elements
+            .iter()
+            .any(|(_, e)| matches!(e, PageElement::Include { .. }))
+        {
+            continue;
+        }
+
+        let locale =
+            split_locale_prefix(&page.key, locale_keys).map(|(locale, _)| locale.to_owned());
+        let mut expansion = IncludeExpansion {
+            sources: &sources,
+            locale,
+            stack: vec![page.link_base.clone()],
+            elements: Vec::new(),
+            issues: Vec::new(),
+        };
+        let elements = std::mem::take(&mut page.elements);
+        for (line, element) in elements {
+            expansion.push(line, &page.link_base, element, false);
+        }
+
+        // own headings & checkboxes keep their anchors/ids, links to them must not break
+        let own_toc = std::mem::take(&mut page.table_of_contents);
+        let mut checkbox_ids: Vec<String> = expansion
+            .elements
+            .iter()
+            .filter_map(|(_, e, included)| match e {
+                PageElement::Checkbox { id, .. } if !included => Some(id.clone()),
+                _ => None,
+            })
+            .collect();
+        for (line, mut element, included) in expansion.elements {
+            match &mut element {
+                PageElement::Heading(entry) => {
+                    if included {
+                        let taken: Vec<TocEntry> = own_toc
+                            .iter()
+                            .chain(&page.table_of_contents)
+                            .cloned()
+                            .collect();
+                        entry.anchor = unique_anchor(&entry.anchor, &taken);
+                    }
+                    page.table_of_contents.push(entry.clone());
+                }
+                PageElement::Checkbox { text, id, .. } if included => {
+                    *id = checklist_item_id(text, &checkbox_ids);
+                    checkbox_ids.push(id.clone());
+                }
+                _ => (),
+            }
+            page.elements.push((line, element));
+        }
+
+        issues.extend(
+            expansion
+                .issues
+                .into_iter()
+                .map(|issue| (page.key.clone(), issue)),
+        );
+    }
+    issues
+}
+
+struct IncludeExpansion<'a> {
+    sources: &'a HashMap<String, ParsedPage>,
+    locale: Option<String>,
+    stack: Vec<String>, // pages currently being included, to detect cycles
+    elements: Vec<(usize, PageElement, bool)>, // bool: comes from another page
+    issues: Vec<ParseIssue>,
+}
+
+impl IncludeExpansion<'_> {
+    // `line` is always the line in the including page, that's where problems get reported
+    fn push(&mut self, line: usize, from_page: &str, element: PageElement, included: bool) {
+        let element = match element {
+            PageElement::Include { raw_target, target } => {
+                if let Err(message) = self.include(line, &target) {
+                    self.issues.push(ParseIssue {
+                        line,
+                        message: format!("<Include={raw_target}> {message}"),
+                    });
+                    self.elements.push((
+                        line,
+                        PageElement::Markdown(format!(
+                            "*⚠ \"{raw_target}\" couldn't be included*\n"
+                        )),
+                        included,
+                    ));
+                }
+                return;
+            }
+            // anchors into the same page have to keep pointing at the included page
+            PageElement::AppLink {
+                text,
+                raw_target,
+                target,
+            } if included && target.starts_with(ANCHOR_SEPARATOR) => PageElement::AppLink {
+                text,
+                raw_target,
+                target: format!("{from_page}{target}"),
+            },
+            // tables are found relative to the page, so they get a path from the root
+            PageElement::Table { file } if included && !file.starts_with(PATH_SEPARATOR) => {
+                let pack_prefix = format!("{PACK_KEY_PREFIX}{PATH_SEPARATOR}");
+                let folder = match from_page.strip_prefix(&pack_prefix) {
+                    Some(pack_page) => parent_folder(
+                        pack_page
+                            .split_once(PATH_SEPARATOR)
+                            .map_or("", |(_, page)| page),
+                    ),
+                    None => parent_folder(from_page),
+                };
+                PageElement::Table {
+                    file: format!("/{folder}/{file}"),
+                }
+            }
+            element => element,
+        };
+        self.elements.push((line, element, included));
+    }
+
+    fn include(&mut self, line: usize, target: &str) -> Result<(), String> {
+        let (target_page, anchor) = split_anchor(target);
+        if self.stack.iter().any(|page| page == target_page) {
+            return Err(format!(
+                "would include itself ({} -> {target_page})",
+                self.stack.join(" -> ")
+            ));
+        }
+        if self.stack.len() > MAX_INCLUDE_DEPTH {
+            return Err(format!(
+                "is nested deeper than {MAX_INCLUDE_DEPTH} includes"
+            ));
+        }
+
+        let source = self
+            .locale
+            .as_ref()
+            .and_then(|locale| {
+                self.sources
+                    .get(&format!("{locale}{PATH_SEPARATOR}{target_page}"))
+            })
+            .or_else(|| self.sources.get(target_page))
+            .ok_or_else(|| format!("there is no page \"{target_page}\""))?;
+
+        let elements: Vec<PageElement> = match anchor {
+            None => source.elements.iter().map(|(_, e)| e.clone()).collect(),
+            Some(anchor) => {
+                // the heading and everything below it, up to the next heading of the same level
+                let start = source
+                    .elements
+                    .iter()
+                    .position(|(_, e)| matches!(e, PageElement::Heading(h) if h.anchor == anchor))
+                    .ok_or_else(|| {
+                        format!("\"{target_page}\" has no heading with anchor #{anchor}")
+                    })?;
+                let PageElement::Heading(heading) = &source.elements[start].1 else {
+                    unreachable!()
+                };
+                source.elements[start..]
+                    .iter()
+                    .enumerate()
+                    .take_while(|(i, (_, e))| {
+                        *i == 0 || !matches!(e, PageElement::Heading(h) if h.level <= heading.level)
+                    })
+                    .map(|(_, (_, e))| e.clone())
+                    .collect()
+            }
+        };
+
+        self.stack.push(target_page.to_owned());
+        for element in elements {
+            self.push(line, &source.link_base, element, true);
+        }
+        self.stack.pop();
+        Ok(())
+    }
+}
+
+/// items are remembered by their text (whitespace & case don't matter), so moving them around or
+/// editing other lines keeps their state; the same text twice gets "#2", "#3", ...
+pub fn checklist_item_id(text: &str, existing: &[String]) -> String {
+    let base = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let mut id = base.clone();
+    let mut n = 1;
+    while existing.contains(&id) {
+        n += 1;
+        id = format!("{base}#{n}");
+    }
+    id
+}
+
+// fills the front matter of the page, returns the number of lines it took up
+fn parse_front_matter(contents: &str, page: &mut ParsedPage) -> usize {
+    const FRONT_MATTER_DELIMITER: &str = "---";
+
+    let mut lines = contents.lines();
+    if lines.next().map(str::trim_end) != Some(FRONT_MATTER_DELIMITER) {
+        return 0;
+    }
+
+    let mut front_matter = FrontMatter::default();
+    for (i, line) in lines.enumerate() {
+        let line_number = i + 2;
+        if line.trim_end() == FRONT_MATTER_DELIMITER {
+            page.front_matter = front_matter;
+            return line_number;
+        }
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue; // yaml comments
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            page.issues.push(ParseIssue {
+                line: line_number,
+                message: format!("front matter line \"{line}\" isn't \"key: value\""),
+            });
+            continue;
+        };
+        let value = value.trim().trim_matches('"').trim();
+        let text = (!value.is_empty()).then(|| value.to_owned());
+
+        match key.trim().to_lowercase().as_str() {
+            "title" => front_matter.title = text,
+            "icon" => front_matter.icon = text,
+            "region" => front_matter.region = text,
+            "tags" => {
+                // "a, b" and "[a, b]" both work
+                front_matter.tags = value
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(',')
+                    .map(|tag| tag.trim().trim_matches('"').trim().to_owned())
+                    .filter(|tag| !tag.is_empty())
+                    .collect();
+            }
+            "order" => match value.parse() {
+                Ok(order) => front_matter.order = Some(order),
+                Err(_) => page.issues.push(ParseIssue {
+                    line: line_number,
+                    message: format!("front matter order \"{value}\" isn't a number"),
+                }),
+            },
+            "hidden" => match value.to_lowercase().as_str() {
+                "true" | "yes" => front_matter.hidden = true,
+                "false" | "no" => front_matter.hidden = false,
+                _ => page.issues.push(ParseIssue {
+                    line: line_number,
+                    message: format!("front matter hidden \"{value}\" isn't true or false"),
+                }),
+            },
+            other => page.issues.push(ParseIssue {
+                line: line_number,
+                message: format!(
+                    "unknown front matter key \"{other}\", known are: title, tags, icon, order, region, hidden"
+                ),
+            }),
+        }
+    }
+
+    // never closed -> it's just markdown starting with a horizontal rule
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_page() {
+        let page_keys = collect_page_keys(["ROOT", "Guides/Ho-Oh-Guide"].into_iter());
+        let contents = "# Start\nsome text\n<AppLink= Guide, Guides/Ho-Oh-Guide#notes>\n<Pokemon=Ho-Oh>\n<Foo=bar>\n<WebLink=broken\n```\n# not a heading\n```";
+        let page = parse_page("ROOT", "ROOT", contents, &page_keys);
+
+        let elements: Vec<&PageElement> = page.elements.iter().map(|(_, e)| e).collect();
+        assert!(matches!(elements[0], PageElement::Heading(h) if h.anchor == "start"));
+        assert_eq!(
+            elements[1],
+            &PageElement::Markdown("some text\n".to_owned())
+        );
+        assert!(
+            matches!(elements[2], PageElement::AppLink { target, .. } if target == "Guides/Ho-Oh-Guide#notes")
+        );
+        assert!(matches!(elements[3], PageElement::DataCard { name, .. } if name == "Ho-Oh"));
+        // unknown and broken tags stay markdown, but get reported
+        assert!(matches!(elements[4], PageElement::Markdown(md) if md.starts_with("<Foo=bar>")));
+        assert_eq!(page.table_of_contents.len(), 1);
+        assert_eq!(
+            page.issues.iter().map(|i| i.line).collect::<Vec<_>>(),
+            [5, 6]
+        );
+    }
+
+    #[test]
+    fn test_checklist_items() {
+        let contents = "- [ ] Catch  Ho-Oh\n* [x] buy Max Repels\n- [ ] catch ho-oh\n`- [ ] no`";
+        let page = parse_page("Ho-Oh-Guide", "Ho-Oh-Guide", contents, &HashSet::new());
+        let ids: Vec<(&str, bool)> = page
+            .elements
+            .iter()
+            .filter_map(|(_, e)| match e {
+                PageElement::Checkbox { id, checked, .. } => Some((id.as_str(), *checked)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                ("catch ho-oh", false),
+                ("buy max repels", true),
+                ("catch ho-oh#2", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_includes() {
+        let files: Vec<(String, String)> = [
+            ("Guide", "# Intro\n<Include=Natures#speed>\n<Include=Loop>"),
+            ("Natures", "# Attack\nAdamant\n# Speed\nJolly\n## Timid\nspecial\n# Defense\n<AppLink=Up, #attack>"),
+            ("DE/Natures", "# Speed\nFroh"),
+            ("DE/Guide", "<Include=Natures#speed>"),
+            ("Loop", "<Include=Guide>"),
+        ]
+        .into_iter()
+        .map(|(k, c)| (k.to_owned(), c.to_owned()))
+        .collect();
+        let locale_keys = ["DE".to_owned()];
+        let mut parsed = parse_resource_files(&files, &locale_keys);
+        let issues = expand_includes(&mut parsed, &locale_keys);
+
+        let guide = &parsed.pages[0];
+        let anchors: Vec<&str> = guide
+            .table_of_contents
+            .iter()
+            .map(|t| t.anchor.as_str())
+            .collect();
+        assert_eq!(anchors, ["intro", "speed", "timid"]);
+        assert!(matches!(&guide.elements[2].1, PageElement::Markdown(md) if md == "Jolly\n"));
+        // Guide -> Loop -> Guide
+        assert_eq!(issues.len(), 2);
+        assert!(
+            issues
+                .iter()
+                .all(|(_, i)| i.message.contains("include itself"))
+        );
+
+        // translations include the translated page
+        assert!(
+            matches!(&parsed.pages[3].elements[1].1, PageElement::Markdown(md) if md == "Froh\n")
+        );
+
+        let mut parsed = parse_resource_files(
+            &[
+                ("A".to_owned(), "<Include=Natures#defense>".to_owned()),
+                files[1].clone(),
+            ],
+            &[],
+        );
+        expand_includes(&mut parsed, &[]);
+        assert!(
+            matches!(&parsed.pages[0].elements[1].1, PageElement::AppLink { target, .. } if target == "Natures#attack")
+        );
+    }
+
+    #[test]
+    fn test_front_matter() {
+        let page_keys = collect_page_keys(["ROOT"].into_iter());
+        let contents = "---\ntitle: \"Ho-Oh Guide\"\ntags: [legendary, pve]\norder: 2\nhidden: maybe\n---\n# Team\n<AppLink=ROOT>";
+        let page = parse_page("Ho-Oh-Guide", "Ho-Oh-Guide", contents, &page_keys);
+
+        assert_eq!(page.front_matter.title.as_deref(), Some("Ho-Oh Guide"));
+        assert_eq!(page.front_matter.tags, ["legendary", "pve"]);
+        assert_eq!(page.front_matter.order, Some(2));
+        assert_eq!(page.issues.len(), 1);
+        assert_eq!(page.issues[0].line, 5);
+        // line numbers keep counting the front matter
+        assert_eq!(page.elements[0].0, 7);
+        assert!(
+            matches!(&page.elements[1].1, PageElement::AppLink { text: None, target, .. } if target == "ROOT")
+        );
+    }
+}
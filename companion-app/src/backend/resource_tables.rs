@@ -0,0 +1,386 @@
+// interactive tables loaded from csv/json files next to the md-files, e.g. <Table=EV-Hordes.csv>
+// sortable by clicking a header, filterable and with hideable columns
+
+use egui::{Label, RichText, Sense, TextEdit, Ui};
+use egui_extras::{Column, TableBuilder};
+use serde::{
+    Deserialize,
+    de::{MapAccess, Visitor},
+};
+use serde_json::Value;
+use std::{cell::RefCell, cmp::Ordering, fmt, fs, path::Path};
+
+use crate::{
+    backend::ressources_feature::{Resource, RessourcesSubsystem},
+    frontend::style,
+};
+
+const TABLE_ROW_HEIGHT: f32 = 18.;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableData {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>, // every row has exactly as many cells as there are headers
+}
+
+impl TableData {
+    fn new(headers: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        let column_count = headers.len();
+        let rows = rows
+            .into_iter()
+            .map(|mut row| {
+                row.resize(column_count, String::new());
+                row
+            })
+            .collect();
+        Self { headers, rows }
+    }
+}
+
+/// reads a .csv or .json table, the error is meant to be shown to the guide author
+pub fn load_table_file(path: &Path) -> Result<TableData, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let table = match extension.as_str() {
+        "csv" => parse_csv_table(&content),
+        "json" => parse_json_table(&content),
+        _ => Err("only .csv and .json tables are supported".to_owned()),
+    };
+    table.map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// first record is the header, fields can be quoted ("a, b" or "say ""hi""")
+pub fn parse_csv_table(content: &str) -> Result<TableData, String> {
+    let mut records = parse_csv_records(content.trim_start_matches('\u{feff}'))?.into_iter();
+    let Some(headers) = records.next() else {
+        return Err("the table is empty".to_owned());
+    };
+    Ok(TableData::new(headers, records.collect()))
+}
+
+fn parse_csv_records(content: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            (',', false) => record.push(std::mem::take(&mut field).trim().to_owned()),
+            ('\r', false) => (),
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field).trim().to_owned());
+                // blank lines are no records
+                if record.len() > 1 || !record[0].is_empty() {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            (c, _) => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("a quoted field is never closed".to_owned());
+    }
+    if !field.trim().is_empty() || !record.is_empty() {
+        record.push(field.trim().to_owned());
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// either an array of objects (keys become the columns, in file order)
+/// or an array of arrays with the header as first row
+pub fn parse_json_table(content: &str) -> Result<TableData, String> {
+    let rows: Vec<JsonRow> = serde_json::from_str(content)
+        .map_err(|e| format!("expected an array of objects or arrays: {e}"))?;
+
+    let mut headers: Vec<String> = Vec::new();
+    let mut table_rows = Vec::new();
+    for row in rows {
+        match row {
+            JsonRow::Array(cells) if headers.is_empty() && table_rows.is_empty() => {
+                headers = cells.iter().map(cell_text).collect();
+            }
+            JsonRow::Array(cells) => table_rows.push(cells.iter().map(cell_text).collect()),
+            JsonRow::Object(OrderedObject(fields)) => {
+                let mut table_row = vec![String::new(); headers.len()];
+                for (key, value) in fields {
+                    let column = match headers.iter().position(|h| *h == key) {
+                        Some(column) => column,
+                        None => {
+                            headers.push(key);
+                            table_row.push(String::new());
+                            headers.len() - 1
+                        }
+                    };
+                    table_row[column] = cell_text(&value);
+                }
+                table_rows.push(table_row);
+            }
+        }
+    }
+    Ok(TableData::new(headers, table_rows))
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonRow {
+    Array(Vec<Value>),
+    Object(OrderedObject),
+}
+
+/// fields of a json object in file order (serde_json's map would sort them)
+struct OrderedObject(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for OrderedObject {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = OrderedObject;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a json object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(field) = map.next_entry::<String, Value>()? {
+                    fields.push(field);
+                }
+                Ok(OrderedObject(fields))
+            }
+        }
+
+        deserializer.deserialize_map(FieldVisitor)
+    }
+}
+
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// numbers compare as numbers ("9" < "10"), everything else case-insensitive
+pub fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+////////////////////////////////////////
+/// Table Resource
+////////////////////////////////////////
+// what the user did with the table, survives as long as the resources aren't reloaded
+#[derive(Default)]
+struct TableView {
+    filter: String,
+    sort_column: Option<usize>,
+    sort_descending: bool,
+    hidden_columns: Vec<bool>,
+}
+
+pub struct TableResource {
+    resource_key: String,
+    table: TableData,
+    view: RefCell<TableView>,
+}
+
+impl TableResource {
+    pub fn new(key: impl Into<String>, table: TableData) -> Self {
+        let view = TableView {
+            hidden_columns: vec![false; table.headers.len()],
+            ..Default::default()
+        };
+        Self {
+            resource_key: key.into(),
+            table,
+            view: RefCell::new(view),
+        }
+    }
+
+    // indices of the rows matching the filter, in the selected order
+    fn visible_rows(&self, view: &TableView) -> Vec<usize> {
+        let filter = view.filter.trim().to_lowercase();
+        let mut rows: Vec<usize> = (0..self.table.rows.len())
+            .filter(|&i| {
+                filter.is_empty()
+                    || self.table.rows[i]
+                        .iter()
+                        .any(|cell| cell.to_lowercase().contains(&filter))
+            })
+            .collect();
+
+        if let Some(column) = view.sort_column {
+            rows.sort_by(|&a, &b| {
+                let ordering =
+                    compare_cells(&self.table.rows[a][column], &self.table.rows[b][column]);
+                match view.sort_descending {
+                    true => ordering.reverse(),
+                    false => ordering,
+                }
+            });
+        }
+        rows
+    }
+}
+
+impl Resource for TableResource {
+    fn get_title(&self) -> String {
+        self.resource_key.clone()
+    }
+
+    fn render_resource(
+        &self,
+        _resource_subsystem: &RessourcesSubsystem,
+        ui: &mut Ui,
+    ) -> Option<String> {
+        let mut view = self.view.borrow_mut();
+        let rows = self.visible_rows(&view);
+
+        // FILTER & COLUMN TOGGLES
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut view.filter)
+                    .hint_text("🔍 Filter")
+                    .desired_width(150.),
+            );
+            ui.menu_button("Columns", |ui| {
+                for (i, header) in self.table.headers.iter().enumerate() {
+                    let mut visible = !view.hidden_columns[i];
+                    if ui.checkbox(&mut visible, header).changed() {
+                        view.hidden_columns[i] = !visible;
+                    }
+                }
+            });
+            ui.label(
+                RichText::new(format!("{} / {} rows", rows.len(), self.table.rows.len())).weak(),
+            );
+        });
+
+        let columns: Vec<usize> = (0..self.table.headers.len())
+            .filter(|&i| !view.hidden_columns[i])
+            .collect();
+        if columns.is_empty() {
+            ui.label("all columns are hidden");
+            return None;
+        }
+
+        let mut clicked_column = None;
+        egui::ScrollArea::horizontal()
+            .id_salt(&self.resource_key)
+            .show(ui, |ui| {
+                TableBuilder::new(ui)
+                    .id_salt(&self.resource_key)
+                    .striped(true)
+                    .vscroll(false)
+                    .columns(Column::auto().resizable(true), columns.len())
+                    .header(TABLE_ROW_HEIGHT + 4., |mut header| {
+                        for &column in &columns {
+                            header.col(|ui| {
+                                let arrow = match (
+                                    view.sort_column == Some(column),
+                                    view.sort_descending,
+                                ) {
+                                    (true, false) => " ⏶",
+                                    (true, true) => " ⏷",
+                                    (false, _) => "",
+                                };
+                                let text =
+                                    RichText::new(format!("{}{arrow}", self.table.headers[column]))
+                                        .strong()
+                                        .color(style::COLOR_HEADING_2);
+                                let response = ui.add(Label::new(text).sense(Sense::click()));
+                                if response.hovered() {
+                                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                                }
+                                if response.clicked() {
+                                    clicked_column = Some(column);
+                                }
+                            });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(TABLE_ROW_HEIGHT, rows.len(), |mut row| {
+                            let cells = &self.table.rows[rows[row.index()]];
+                            for &column in &columns {
+                                row.col(|ui| {
+                                    ui.label(&cells[column]);
+                                });
+                            }
+                        });
+                    });
+            });
+
+        // first click sorts ascending, second descending, third restores the file order
+        if let Some(column) = clicked_column {
+            match (view.sort_column == Some(column), view.sort_descending) {
+                (false, _) => (view.sort_column, view.sort_descending) = (Some(column), false),
+                (true, false) => view.sort_descending = true,
+                (true, true) => (view.sort_column, view.sort_descending) = (None, false),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_table() {
+        let table = parse_csv_table(
+            "Pokemon, EV, Route\n\"Ho-Oh, shiny\",3,\"say \"\"hi\"\"\"\n\nZubat,1\n",
+        )
+        .unwrap();
+        assert_eq!(table.headers, ["Pokemon", "EV", "Route"]);
+        assert_eq!(table.rows[0], ["Ho-Oh, shiny", "3", "say \"hi\""]);
+        assert_eq!(table.rows[1], ["Zubat", "1", ""]); // short rows are padded
+        assert!(parse_csv_table("a,\"b\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_json_table() {
+        let objects = parse_json_table(
+            r#"[{"name": "Zubat", "ev": 1}, {"name": "Abra", "route": null, "ev": 1}]"#,
+        )
+        .unwrap();
+        assert_eq!(objects.headers, ["name", "ev", "route"]);
+        assert_eq!(objects.rows[0], ["Zubat", "1", ""]);
+
+        let arrays = parse_json_table(r#"[["name", "ev"], ["Zubat", 1]]"#).unwrap();
+        assert_eq!(arrays.headers, ["name", "ev"]);
+        assert_eq!(arrays.rows, [["Zubat", "1"]]);
+    }
+
+    #[test]
+    fn test_compare_cells() {
+        assert_eq!(compare_cells("9", "10"), Ordering::Less);
+        assert_eq!(compare_cells("abra", "Zubat"), Ordering::Less);
+        assert_eq!(compare_cells("5", "-"), Ordering::Less);
+    }
+}
@@ -1,27 +1,120 @@
 use crate::frontend::style;
 use crate::{
+    backend::{
+        game_data::GameData,
+        locales::{self, LocaleSubsystem, TextCategory},
+        resource_cards::{
+            ItemCardResource, LocationCardResource, MoveCardResource, PokemonCardResource,
+        },
+        resource_packs::{self, PACK_KEY_PREFIX},
+        resource_parser::{self, FrontMatter, PageElement},
+        resource_tables::{self, TableResource},
+        settings::DEFAULT_UI_LOCALE,
+    },
     frontend::utils as frontend_utils,
+    tr,
     utils::{self as global_utils, find_asset_folder},
 };
-use egui::{Label, Sense, TextStyle, Ui, Vec2};
+use egui::{Align, Label, Sense, TextStyle, Ui, Vec2};
 use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
 use regex::Regex;
-use std::{cell::RefCell, collections::HashMap, io, path::PathBuf};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    io,
+    path::PathBuf,
+    rc::Rc,
+};
+
+/// separates the page key from an in-page heading anchor in resource links (e.g. "Ho-Oh-Guide#notes")
+pub const ANCHOR_SEPARATOR: char = '#';
+/// separates folders in path-qualified resource keys (e.g. "Guides/Ho-Oh-Guide")
+pub const PATH_SEPARATOR: char = '/';
+pub const ROOT_RESOURCE: &str = "ROOT";
+/// generated pages listing all pages, by tag and by region live below this key
+pub const INDEX_RESOURCE: &str = "index";
+const RECENT_RESOURCES_MAX: usize = 10;
+
+/// one entry in the history of visited resources, remembers where the user had scrolled to
+#[derive(Debug, Clone)]
+pub struct VisitedResource {
+    pub key: String,
+    pub scroll_offset: f32,
+}
+
+/// one heading of a resource page, used to build its table of contents
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocEntry {
+    pub level: usize,
+    pub text: String,
+    pub anchor: String,
+}
 
 pub struct RessourcesSubsystem {
     available_ressources: HashMap<String, Box<dyn Resource>>,
 
     current_resource: Option<String>,
 
-    visited_resources: Vec<String>,
+    visited_resources: Vec<VisitedResource>,
+    forward_resources: Vec<VisitedResource>, // filled by going back, cleared by any new navigation
+    bookmarks: Vec<String>,
+    recent_resources: Vec<String>, // most recent first, without duplicates
+
+    // problems found while loading (e.g. key collisions), shown to the user in the nav bar
+    load_issues: Vec<String>,
+
+    pub show_table_of_contents: bool,
+
+    // scroll state of the resources window, written back by the gui every frame
+    current_scroll_offset: f32,
+    pending_scroll_offset: Option<f32>,
+    // consumed by the HeadingResource with the matching anchor while rendering
+    pending_scroll_anchor: RefCell<Option<String>>,
+
+    // base data and translations for data cards (<Pokemon=...> etc.)
+    game_data: GameData,
+    locale_subsystem: Rc<LocaleSubsystem>,
+    display_locale: String,
+
+    // translated variants of pages: locale key -> page keys available in resources/<LOCALE>/
+    localized_pages: HashMap<String, BTreeSet<String>>,
+    // every page backed by a md-file in the default language, what translations are measured by
+    translatable_pages: BTreeSet<String>,
+
+    // "- [ ]" items: page -> (item id, checked in the file) and what the user ticked since
+    checklist_items: HashMap<String, Vec<(String, bool)>>,
+    checklist_state: RefCell<BTreeMap<String, BTreeMap<String, bool>>>,
+
+    // front matter of every page (and a title for generated ones), also of translated pages
+    page_info: HashMap<String, FrontMatter>,
+
+    // install_counter of the ResourcePackSubsystem the resources were last loaded with
+    loaded_pack_generation: usize,
 }
 
 impl RessourcesSubsystem {
-    pub fn new() -> Self {
+    pub fn new(locale_subsystem: Rc<LocaleSubsystem>) -> Self {
         let mut ressources_subsystem = Self {
             available_ressources: HashMap::new(),
             current_resource: None,
             visited_resources: Vec::new(),
+            forward_resources: Vec::new(),
+            bookmarks: Vec::new(),
+            recent_resources: Vec::new(),
+            load_issues: Vec::new(),
+            show_table_of_contents: false,
+            current_scroll_offset: 0.,
+            pending_scroll_offset: None,
+            pending_scroll_anchor: RefCell::new(None),
+            game_data: GameData::load(),
+            locale_subsystem,
+            display_locale: String::new(),
+            localized_pages: HashMap::new(),
+            translatable_pages: BTreeSet::new(),
+            checklist_items: HashMap::new(),
+            checklist_state: RefCell::new(BTreeMap::new()),
+            page_info: HashMap::new(),
+            loaded_pack_generation: 0,
         };
 
         ressources_subsystem.load_resources();
@@ -50,7 +143,7 @@ impl RessourcesSubsystem {
 
         println!("\nResourceSubsystem - loading resources from disk ...\n");
 
-        let resources_dir = self.resource_folder_path();
+        let resources_dir = Self::resource_folder_path();
 
         let md_file_list = match resources_dir {
             Ok(dir) => global_utils::read_in_all_markdown_files(dir),
@@ -58,7 +151,9 @@ impl RessourcesSubsystem {
         };
 
         match md_file_list {
-            Ok(list) => {
+            Ok(mut list) => {
+                list.extend(read_in_resource_packs());
+
                 // DEBUG Output
                 println!("Files found:");
                 list.iter().for_each(|(file, _cont)| println!("  - {file}"));
@@ -68,14 +163,48 @@ impl RessourcesSubsystem {
             Err(err) => {
                 // add one "error" resource, which explains what went wrong to the user
                 self.add_markdown_resource(
-                    "ROOT",
+                    ROOT_RESOURCE,
                     format!("Error during .md file read: \n{0}", err),
                 );
             }
         };
     }
 
-    fn resource_folder_path(&mut self) -> Result<PathBuf, io::Error> {
+    /// throws away all loaded resources and reads them from disk again (e.g. after installing a
+    /// resource pack), stays on the current page if it still exists
+    pub fn reload_resources(&mut self) {
+        let current_resource = self.current_resource.take();
+
+        self.available_ressources.clear();
+        self.load_issues.clear();
+        self.localized_pages.clear();
+        self.translatable_pages.clear();
+        self.page_info.clear();
+        self.checklist_items.clear();
+        self.load_resources();
+
+        match current_resource {
+            Some(key) if self.available_ressources.contains_key(&key) => {
+                self.current_resource = Some(key);
+            }
+            _ => {
+                self.visited_resources.clear();
+                self.forward_resources.clear();
+                self.set_root_resource();
+            }
+        }
+    }
+
+    /// reloads when resource packs were installed/removed since the last load
+    pub fn sync_with_resource_packs(&mut self, pack_generation: usize) {
+        if self.loaded_pack_generation != pack_generation {
+            self.loaded_pack_generation = pack_generation;
+            self.reload_resources();
+        }
+    }
+
+    /// assets/resources, where the user written md-files live
+    pub fn resource_folder_path() -> Result<PathBuf, io::Error> {
         match find_asset_folder() {
             Ok(assets_folder) => {
                 let resource_candidate = assets_folder.join("resources");
@@ -93,102 +222,301 @@ impl RessourcesSubsystem {
     }
 
     fn parse_into_resources(&mut self, md_file_list: Vec<(String, String)>) {
-        // regex building should never fail
-        let regex_search_for_res_tag = Regex::new(r"<([^=]+)=([^,>]+)(?:,([^,>]+))?>").unwrap();
+        // resources/<LOCALE>/... are translations of pages, not a category of their own
+        let locale_keys = locales::read_locale_keys_from_disk();
+        for (file_name, _) in &md_file_list {
+            match split_locale_prefix(file_name, &locale_keys) {
+                Some((locale, page)) => {
+                    self.localized_pages
+                        .entry(locale.to_owned())
+                        .or_default()
+                        .insert(page.to_owned());
+                }
+                None if !file_name.starts_with(&format!("{PACK_KEY_PREFIX}{PATH_SEPARATOR}")) => {
+                    self.translatable_pages.insert(file_name.clone());
+                }
+                None => (),
+            }
+        }
+
+        let mut parsed = resource_parser::parse_resource_files(&md_file_list, &locale_keys);
+        for (page, issue) in resource_parser::expand_includes(&mut parsed, &locale_keys) {
+            let issue = format!("{page}.md line {}: {}", issue.line, issue.message);
+            eprintln!("ResourceSubsystem - {issue}");
+            self.load_issues.push(issue);
+        }
 
-        for entry in md_file_list {
-            let file_name = entry.0;
-            let file_contents = entry.1;
+        // what the generated index pages list, translations are shown through their original
+        let mut index_pages = Vec::new();
+
+        for page in parsed.pages {
+            let file_name = page.key;
+            if !page.front_matter.hidden && split_locale_prefix(&file_name, &locale_keys).is_none()
+            {
+                index_pages.push(file_name.clone());
+            }
+            self.page_info.insert(file_name.clone(), page.front_matter);
 
             println!("\n============= Parsing File: {file_name} ==============================");
 
             let mut ordered_res_keys: Vec<String> = Vec::new();
-            let mut md_content: String = String::default();
             let mut md_res_count: i32 = 0; // used to create unique resource names
-
-            // needs to be a local fn with parameters, declaring as closure creates borrow issues
-            // markdown is collected line by line and added as one resource whenever this fn is called
-            fn add_collected_markdown(
-                md_content: &mut String,
-                file_name: &String,
-                md_res_count: &mut i32,
-                ordered_res_keys: &mut Vec<String>,
-                this: &mut RessourcesSubsystem,
-            ) {
-                if !md_content.is_empty() {
-                    let md_res_key =
-                        format!("inner_{0}_md_{1}", file_name.to_owned(), md_res_count);
-                    *md_res_count += 1;
-                    this.add_markdown_resource(&md_res_key, md_content.clone());
-                    ordered_res_keys.push(md_res_key.to_owned());
-                    println!(
-                        "- Markdown Section (key: {0})\n------------------------------------------\n{1}\n------------------------------------------",
-                        &md_res_key, &md_content
-                    );
-                    md_content.clear();
-                }
+            let mut link_res_count: i32 = 0; // same for app & web links & data cards
+            let mut heading_count: i32 = 0;
+            let mut checkbox_count: i32 = 0;
+
+            for issue in &page.issues {
+                let issue = format!("{file_name}.md line {}: {}", issue.line, issue.message);
+                eprintln!("ResourceSubsystem - {issue}");
+                self.load_issues.push(issue);
             }
 
-            // parse line by line, checking for reserved resource tags or collecting as generic markdown
-            for line in file_contents.lines() {
-                let mut inserted_res: Option<String> = None;
-                // check for resource tag
-                if let Some(res_tag) = regex_search_for_res_tag.captures(line) {
-                    let res_type: &str = res_tag[1].trim(); // are guaranteed to exist
-                    let res_text: &str = res_tag[2].trim(); // otherwise would regex not match
-                    let res_data: &str = res_tag.get(3).map(|m| m.as_str()).unwrap_or("").trim();
-
-                    println!(
-                        "- {1} : \"{2}\" » \"{3}\" from : \"{0}\"",
-                        &res_tag[0], &res_type, &res_text, &res_data
-                    );
-
-                    match res_type {
-                        "AppLink" => {
-                            inserted_res = Some(self.add_app_link(res_text, res_data.to_owned()));
-                        }
-                        "WebLink" => {
-                            inserted_res =
-                                Some(self.add_web_link_resource(res_text, res_data.to_owned()));
-                        }
-                        _ => {
-                            inserted_res = None;
+            for (_, element) in page.elements {
+                // inline resources are namespaced by their page, so equal link texts never collide
+                let link_res_key = format!("inner_{file_name}_link_{link_res_count}");
+                let inserted_res_key = match element {
+                    PageElement::Markdown(md_content) => {
+                        let md_res_key = format!("inner_{file_name}_md_{md_res_count}");
+                        md_res_count += 1;
+                        println!(
+                            "- Markdown Section (key: {0})\n------------------------------------------\n{1}\n------------------------------------------",
+                            &md_res_key, &md_content
+                        );
+                        self.add_markdown_resource(md_res_key, md_content)
+                    }
+                    PageElement::Heading(heading) => {
+                        let heading_key = format!("inner_{file_name}_heading_{heading_count}");
+                        heading_count += 1;
+                        println!(
+                            "- Heading (level {}) : \"{}\" » #{}",
+                            heading.level, heading.text, heading.anchor
+                        );
+                        self.add_heading_resource(
+                            heading_key,
+                            heading.level,
+                            heading.text,
+                            heading.anchor,
+                        )
+                    }
+                    PageElement::AppLink { text, target, .. } => {
+                        link_res_count += 1;
+                        self.add_app_link(link_res_key, text, target)
+                    }
+                    PageElement::WebLink { text, url } => {
+                        println!("- WebLink : \"{text}\" » \"{url}\"");
+                        link_res_count += 1;
+                        self.add_web_link_resource(link_res_key, text, url)
+                    }
+                    PageElement::DataCard {
+                        tag_type,
+                        name,
+                        extra,
+                    } => {
+                        println!("- {tag_type} card : \"{name}\"");
+                        link_res_count += 1;
+                        let card: Box<dyn Resource> = match tag_type.as_str() {
+                            "Pokemon" => Box::new(PokemonCardResource::new(&link_res_key, name)),
+                            "Item" => Box::new(ItemCardResource::new(&link_res_key, name)),
+                            "Move" => Box::new(MoveCardResource::new(&link_res_key, name)),
+                            _ => Box::new(LocationCardResource::new(&link_res_key, name, extra)),
+                        };
+                        self.insert_resource(link_res_key, card)
+                    }
+                    PageElement::Checkbox { text, id, checked } => {
+                        let checkbox_key = format!("inner_{file_name}_checkbox_{checkbox_count}");
+                        checkbox_count += 1;
+                        self.checklist_items
+                            .entry(file_name.clone())
+                            .or_default()
+                            .push((id.clone(), checked));
+                        let checkbox = ChecklistItemResource::new(
+                            &checkbox_key,
+                            &file_name,
+                            text,
+                            id,
+                            checked,
+                        );
+                        self.insert_resource(checkbox_key, Box::new(checkbox))
+                    }
+                    // already replaced by the included elements in expand_includes
+                    PageElement::Include { .. } => continue,
+                    PageElement::Table { file } => {
+                        link_res_count += 1;
+                        let table = find_table_file(&file_name, &page.link_base, &file)
+                            .and_then(|path| resource_tables::load_table_file(&path));
+                        match table {
+                            Ok(table) => self.insert_resource(
+                                link_res_key.clone(),
+                                Box::new(TableResource::new(link_res_key, table)),
+                            ),
+                            Err(e) => {
+                                let issue = format!("{file_name}.md table {e}");
+                                eprintln!("ResourceSubsystem - {issue}");
+                                self.load_issues.push(issue);
+                                self.add_markdown_resource(
+                                    link_res_key,
+                                    format!("*⚠ table \"{file}\" couldn't be loaded*"),
+                                )
+                            }
                         }
                     }
-                }
+                };
+                ordered_res_keys.push(inserted_res_key);
+            }
 
-                if let Some(inserted_res_key) = inserted_res {
-                    // add accumulated markdown resource before
-                    add_collected_markdown(
-                        &mut md_content,
-                        &file_name,
-                        &mut md_res_count,
-                        &mut ordered_res_keys,
-                        self,
-                    );
-
-                    // now add inserted resource
-                    ordered_res_keys.push(inserted_res_key.to_owned());
-                    continue;
-                }
+            self.add_resource_collection(file_name, ordered_res_keys, page.table_of_contents);
+            println!("=================================================================")
+        }
+
+        let page_keys = parsed.page_keys;
+        self.add_category_pages(&page_keys);
+        self.add_index_pages(index_pages);
+
+        // "packs" is linked from ROOT, so it should exist even without any installed pack
+        if !self.available_ressources.contains_key(PACK_KEY_PREFIX) {
+            self.add_markdown_resource(
+                PACK_KEY_PREFIX,
+                tr!(
+                    "No resource packs installed yet.\n\nCommunity resource packs can be installed in the settings."
+                ),
+            );
+        }
+    }
+
+    // every folder becomes a category page linking to its content, unless a page with the same
+    // key (e.g. "Guides.md" next to "Guides/") already acts as a hand-written category page
+    fn add_category_pages(&mut self, page_keys: &HashSet<String>) {
+        let folders: BTreeSet<&String> = page_keys
+            .iter()
+            .filter(|key| {
+                page_keys
+                    .iter()
+                    .any(|other| parent_folder(other) == key.as_str())
+            })
+            .collect();
+
+        for folder in folders {
+            if self.available_ressources.contains_key(folder) {
+                continue;
+            }
+
+            // sub-categories first, then pages, both in their front matter order
+            let mut children: Vec<String> = page_keys
+                .iter()
+                .filter(|key| parent_folder(key) == folder.as_str())
+                .filter(|key| self.page_info.get(*key).is_none_or(|info| !info.hidden))
+                .cloned()
+                .collect();
+            self.sort_pages(&mut children);
+            children.sort_by_key(|key| !folders_contain(page_keys, key)); // stable
+
+            self.add_link_list_page(folder.clone(), resource_display_name(folder), &children);
+        }
+    }
+
+    // index/all, index/tags/<tag> and index/regions/<region>, so not every page has to be
+    // hand-linked from ROOT
+    fn add_index_pages(&mut self, mut pages: Vec<String>) {
+        if self.available_ressources.contains_key(INDEX_RESOURCE) {
+            let issue = format!("\"{INDEX_RESOURCE}\" replaces the generated index pages");
+            eprintln!("ResourceSubsystem - {issue}");
+            self.load_issues.push(issue);
+            return;
+        }
+        self.sort_pages(&mut pages);
+
+        // (display name, pages) by lowercase tag/region, so "PvE" and "pve" end up together
+        let mut tags: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+        let mut regions: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+        for page in &pages {
+            let Some(info) = self.page_info.get(page) else {
+                continue;
+            };
+            for tag in &info.tags {
+                tags.entry(index_key_segment(tag))
+                    .or_insert_with(|| (tag.clone(), Vec::new()))
+                    .1
+                    .push(page.clone());
+            }
+            if let Some(region) = &info.region {
+                regions
+                    .entry(index_key_segment(region))
+                    .or_insert_with(|| (region.clone(), Vec::new()))
+                    .1
+                    .push(page.clone());
+            }
+        }
 
-                // no resource tag => treat as regular markdown & collect
-                md_content.push_str(line); // just add to next markdown resource
-                md_content.push('\n'); // line does not include the original line break
+        let all_key = format!("{INDEX_RESOURCE}/all");
+        self.add_link_list_page(all_key.clone(), &tr!("All Pages"), &pages);
+
+        let mut overview = vec![all_key];
+        for (group, title, entries) in [("tags", "Tags", tags), ("regions", "Regions", regions)] {
+            let group_key = format!("{INDEX_RESOURCE}/{group}");
+            let mut entry_keys = Vec::new();
+            for (segment, (name, entry_pages)) in entries {
+                let entry_key = format!("{group_key}/{segment}");
+                self.add_link_list_page(entry_key.clone(), &name, &entry_pages);
+                entry_keys.push(entry_key);
             }
+            self.add_link_list_page(group_key.clone(), &tr!(title), &entry_keys);
+            overview.push(group_key);
+        }
 
-            // last element could be a collected but uncreated markdown resource
-            add_collected_markdown(
-                &mut md_content,
-                &file_name,
-                &mut md_res_count,
-                &mut ordered_res_keys,
-                self,
+        self.add_link_list_page(INDEX_RESOURCE.to_owned(), &tr!("Index"), &overview);
+    }
+
+    // generated page with a title and one AppLink per page, the links show the page titles
+    fn add_link_list_page(&mut self, key: String, title: &str, pages: &[String]) {
+        let mut ordered_res_keys = vec![
+            self.add_markdown_resource(format!("inner_{key}_list_md"), format!("**{title}**")),
+        ];
+        if pages.is_empty() {
+            ordered_res_keys.push(
+                self.add_markdown_resource(
+                    format!("inner_{key}_list_empty_md"),
+                    tr!("*nothing yet*"),
+                ),
             );
+        }
+        for (i, page) in pages.iter().enumerate() {
+            ordered_res_keys.push(self.add_app_link(
+                format!("inner_{key}_list_link_{i}"),
+                None,
+                page.clone(),
+            ));
+        }
 
-            self.add_resource_collection(file_name.to_owned(), ordered_res_keys);
-            println!("=================================================================")
+        self.page_info.entry(key.clone()).or_insert(FrontMatter {
+            title: Some(title.to_owned()),
+            ..Default::default()
+        });
+        self.add_resource_collection(key, ordered_res_keys, Vec::new());
+    }
+
+    // front matter order first (pages without one last), then by title
+    fn sort_pages(&self, pages: &mut [String]) {
+        pages.sort_by_cached_key(|page| {
+            let order = self.page_info.get(page).and_then(|info| info.order);
+            (
+                order.is_none(),
+                order.unwrap_or_default(),
+                self.get_page_title(page).to_lowercase(),
+            )
+        });
+    }
+
+    // inserts a resource, but never silently replaces an existing one
+    // returns the key it was added under for convenience
+    fn insert_resource(&mut self, key: String, resource: Box<dyn Resource>) -> String {
+        if self.available_ressources.contains_key(&key) {
+            let issue = format!("duplicate resource key \"{key}\", only the first one is used");
+            eprintln!("ResourceSubsystem - {issue}");
+            self.load_issues.push(issue);
+        } else {
+            self.available_ressources.insert(key.clone(), resource);
         }
+        key
     }
 
     // creates and adds a resource collection
@@ -197,6 +525,7 @@ impl RessourcesSubsystem {
         &mut self,
         title: impl Into<String>,
         resource_list: Vec<impl Into<String>>,
+        table_of_contents: Vec<TocEntry>,
     ) -> String {
         let col_title = title.into();
         let resource_collection = ResourceCollection::new(
@@ -205,44 +534,47 @@ impl RessourcesSubsystem {
                 .into_iter()
                 .map(|r| -> String { Into::<String>::into(r) })
                 .collect(),
+            table_of_contents,
         );
 
-        self.available_ressources.insert(
-            resource_collection.get_title(),
-            Box::new(resource_collection),
-        );
-        col_title
+        self.insert_resource(col_title, Box::new(resource_collection))
     }
     // creates and adds a web link resource
     // then returns the key it was added under for convenience
-    fn add_web_link_resource(&mut self, text: impl Into<String>, url: impl Into<String>) -> String {
-        let title = text.into();
-        let link = WebLinkResource::new(&title, url);
-        self.available_ressources
-            .insert(title.to_owned(), Box::new(link));
-        title
+    fn add_web_link_resource(
+        &mut self,
+        res_key: impl Into<String>,
+        text: impl Into<String>,
+        url: impl Into<String>,
+    ) -> String {
+        let link = WebLinkResource::new(res_key, text, url);
+        self.insert_resource(link.get_title(), Box::new(link))
     }
 
     // creates and adds a app link resource
     // then returns the key it was added under for convenience
-    // This key can be different if a resource name collision was fixed!
-    fn add_app_link(&mut self, text: impl Into<String>, link_to: impl Into<String>) -> String {
-        let mut title = text.into();
-        let link: String = link_to.into();
-
-        if title.eq(&link) {
-            // fix up name collision between resource link_to and this link
-            title.push_str("_AppLink");
-            println!(
-                "---> Resource Name Collision Fixup: {0} <-- +_AppLink",
-                &title
-            );
-        }
+    fn add_app_link(
+        &mut self,
+        res_key: impl Into<String>,
+        text: Option<String>,
+        link_to: impl Into<String>,
+    ) -> String {
+        let app_link = AppLinkResource::new(res_key, text, link_to);
+        self.insert_resource(app_link.get_title(), Box::new(app_link))
+    }
 
-        let app_link = AppLinkResource::new(&title, &link);
-        self.available_ressources
-            .insert(app_link.get_title(), Box::new(app_link));
-        title
+    // creates and adds a heading resource
+    // then returns the key it was added under for convenience
+    fn add_heading_resource(
+        &mut self,
+        res_key: impl Into<String>,
+        level: usize,
+        heading_text: impl Into<String>,
+        anchor: impl Into<String>,
+    ) -> String {
+        let key = res_key.into();
+        let heading = HeadingResource::new(&key, level, heading_text, anchor);
+        self.insert_resource(key, Box::new(heading))
     }
 
     // creates and adds a markdown resource
@@ -254,44 +586,312 @@ impl RessourcesSubsystem {
     ) -> String {
         let title = res_title.into();
         let markdown = MarkdownResource::new(&title, markdown_text);
-        self.available_ressources
-            .insert(markdown.get_title(), Box::new(markdown));
-        title
+        self.insert_resource(title, Box::new(markdown))
     }
 
     fn set_root_resource(&mut self) {
-        self.set_current_resource("ROOT", false);
+        self.set_current_resource(ROOT_RESOURCE, false);
     }
 
+    /// accepts plain resource keys, "Page#heading" links or "#heading" links into the current page
     pub fn set_current_resource(&mut self, name: impl Into<String>, save_last_in_history: bool) {
         let name_string: String = name.into();
-        if self
+        let (page, anchor) = split_anchor(&name_string);
+        let page = match (page.is_empty(), &self.current_resource) {
+            (true, Some(cur_res)) => cur_res.clone(),
+            _ => page.to_owned(),
+        };
+
+        if !self.available_ressources.contains_key(&page) {
+            return;
+        }
+        let is_current = self
             .current_resource
             .as_ref()
-            .is_none_or(|cur_res| !cur_res.eq(&name_string)) // is not current and is valid
-            && self.available_ressources.contains_key(&name_string)
-        {
-            // commit to switching resource
-            if save_last_in_history {
-                self.current_resource.as_ref().inspect(|cur_res| {
-                    let old_res = (*cur_res).clone();
-                    // maybe check for loops?
-                    self.visited_resources.push(old_res);
-                });
-            }
-            self.current_resource = Some(name_string);
+            .is_some_and(|cur_res| cur_res.eq(&page));
+        if is_current && anchor.is_none() {
+            return;
         }
+
+        // commit to switching resource (or jumping within it)
+        if save_last_in_history && let Some(cur_res) = &self.current_resource {
+            // maybe check for loops?
+            self.visited_resources.push(VisitedResource {
+                key: cur_res.clone(),
+                scroll_offset: self.current_scroll_offset,
+            });
+            // like in a browser, navigating somewhere new drops the forward history
+            self.forward_resources.clear();
+        }
+        if !is_current {
+            self.push_recent_resource(&page);
+            self.current_resource = Some(page);
+            self.pending_scroll_offset = Some(0.); // new pages start at the top
+        }
+        *self.pending_scroll_anchor.borrow_mut() = anchor.map(str::to_owned);
     }
 
     pub fn go_back_visited_resources(&mut self) {
         let last_res_possible = self.visited_resources.pop();
         if let Some(last_res) = last_res_possible {
-            self.set_current_resource(last_res, false);
+            if let Some(cur_res) = &self.current_resource {
+                self.forward_resources.push(VisitedResource {
+                    key: cur_res.clone(),
+                    scroll_offset: self.current_scroll_offset,
+                });
+            }
+            self.set_current_resource(last_res.key, false);
+            // restore where the user was scrolled to before leaving
+            self.pending_scroll_offset = Some(last_res.scroll_offset);
+        };
+    }
+
+    pub fn go_forward_visited_resources(&mut self) {
+        let next_res_possible = self.forward_resources.pop();
+        if let Some(next_res) = next_res_possible {
+            if let Some(cur_res) = &self.current_resource {
+                self.visited_resources.push(VisitedResource {
+                    key: cur_res.clone(),
+                    scroll_offset: self.current_scroll_offset,
+                });
+            }
+            self.set_current_resource(next_res.key, false);
+            self.pending_scroll_offset = Some(next_res.scroll_offset);
         };
     }
 
+    pub fn inspect_next_resource(&self) -> Option<String> {
+        self.forward_resources.last().map(|v| v.key.clone())
+    }
+
+    fn push_recent_resource(&mut self, key: &str) {
+        self.recent_resources.retain(|recent| recent != key);
+        self.recent_resources.insert(0, key.to_owned());
+        self.recent_resources.truncate(RECENT_RESOURCES_MAX);
+    }
+
+    pub fn get_recent_resources(&self) -> &[String] {
+        &self.recent_resources
+    }
+
+    pub fn get_bookmarks(&self) -> &[String] {
+        &self.bookmarks
+    }
+
+    pub fn is_bookmarked(&self, key: &str) -> bool {
+        self.bookmarks.iter().any(|b| b == key)
+    }
+
+    /// pins or unpins the given page
+    pub fn toggle_bookmark(&mut self, key: &str) {
+        match self.is_bookmarked(key) {
+            true => self.bookmarks.retain(|b| b != key),
+            false => self.bookmarks.push(key.to_owned()),
+        }
+    }
+
+    pub fn get_current_resource_key(&self) -> Option<&String> {
+        self.current_resource.as_ref()
+    }
+
+    /// (current page, back history, forward history) keys, e.g. for the SaveState
+    pub fn get_history(&self) -> (String, Vec<String>, Vec<String>) {
+        let keys = |stack: &Vec<VisitedResource>| stack.iter().map(|v| v.key.clone()).collect();
+        (
+            self.current_resource.clone().unwrap_or_default(),
+            keys(&self.visited_resources),
+            keys(&self.forward_resources),
+        )
+    }
+
+    /// restores persisted navigation state, pages which don't exist anymore are dropped
+    pub fn restore_history(
+        &mut self,
+        current: String,
+        back: Vec<String>,
+        forward: Vec<String>,
+        bookmarks: Vec<String>,
+        recent: Vec<String>,
+    ) {
+        let to_visited = |keys: Vec<String>| -> Vec<VisitedResource> {
+            keys.into_iter()
+                .filter(|key| self.available_ressources.contains_key(key))
+                .map(|key| VisitedResource {
+                    key,
+                    scroll_offset: 0.,
+                })
+                .collect()
+        };
+        self.visited_resources = to_visited(back);
+        self.forward_resources = to_visited(forward);
+        self.bookmarks = bookmarks
+            .into_iter()
+            .filter(|key| self.available_ressources.contains_key(key))
+            .collect();
+        self.recent_resources = recent
+            .into_iter()
+            .filter(|key| self.available_ressources.contains_key(key))
+            .take(RECENT_RESOURCES_MAX)
+            .collect();
+
+        if self.available_ressources.contains_key(&current) {
+            self.current_resource = Some(current);
+            self.pending_scroll_offset = Some(0.);
+        }
+    }
+
     pub fn inspect_last_resource(&self) -> Option<String> {
-        self.visited_resources.last().cloned()
+        self.visited_resources.last().map(|v| v.key.clone())
+    }
+
+    /// the gui reports the scroll offset of the resources window here every frame
+    pub fn set_current_scroll_offset(&mut self, offset: f32) {
+        self.current_scroll_offset = offset;
+    }
+
+    /// returns a scroll offset the resources window should jump to once
+    pub fn take_pending_scroll_offset(&mut self) -> Option<f32> {
+        self.pending_scroll_offset.take()
+    }
+
+    /// true (only once) if the current page was requested to scroll to the given anchor
+    pub fn take_pending_scroll_anchor(&self, anchor: &str) -> bool {
+        let mut pending = self.pending_scroll_anchor.borrow_mut();
+        if pending.as_deref() == Some(anchor) {
+            *pending = None;
+            return true;
+        }
+        false
+    }
+
+    pub fn get_game_data(&self) -> &GameData {
+        &self.game_data
+    }
+
+    /// locale the data cards and generated pages are rendered in, follows the "UI Language" setting
+    pub fn set_display_locale(&mut self, locale_key: &str) {
+        if self.display_locale != locale_key {
+            self.display_locale = locale_key.to_owned();
+            // generated pages (index, packs ...) got the UI texts of the language they were built in
+            self.reload_resources();
+        }
+    }
+
+    /// translates a data key (lowercase english name) into the display locale
+    /// falls back to a capitalized key while locales are still loading or the key is unknown
+    pub fn localize(&self, key: &str) -> String {
+        self.locale_subsystem
+            .with_locale(&self.display_locale, |locale| {
+                locale.find_localized_text(key)
+            })
+            .filter(|text| text != key)
+            .unwrap_or_else(|| capitalize_words(key))
+    }
+
+    /// like localize, but resolves the text by the stable id of the data (e.g. a pokedex id)
+    /// locales without ids fall back to the name in the same category, then to localize
+    pub fn localize_id(&self, category: TextCategory, id: u32, name: &str) -> String {
+        self.locale_subsystem
+            .with_locale(&self.display_locale, |locale| {
+                locale
+                    .get_text_by_id(category, id)
+                    .or_else(|| locale.get_text(category, name))
+                    .map(str::to_owned)
+            })
+            .flatten()
+            .unwrap_or_else(|| self.localize(name))
+    }
+
+    pub fn get_load_issues(&self) -> &[String] {
+        &self.load_issues
+    }
+
+    /// (key, title) of every category above the current page, starting at ROOT
+    pub fn get_breadcrumbs(&self) -> Vec<(String, String)> {
+        let mut breadcrumbs = vec![(ROOT_RESOURCE.to_owned(), self.get_page_title(ROOT_RESOURCE))];
+        let Some(current) = &self.current_resource else {
+            return breadcrumbs;
+        };
+        if current == ROOT_RESOURCE {
+            return breadcrumbs;
+        }
+
+        let mut key = String::new();
+        for segment in current.split(PATH_SEPARATOR) {
+            if !key.is_empty() {
+                key.push(PATH_SEPARATOR);
+            }
+            key.push_str(segment);
+            breadcrumbs.push((key.clone(), self.get_page_title(&key)));
+        }
+        breadcrumbs
+    }
+
+    /// front matter title (with icon) in the display locale, falls back to the file name
+    pub fn get_page_title(&self, page: &str) -> String {
+        let info = self
+            .page_info
+            .get(&self.localized_key(page))
+            .or_else(|| self.page_info.get(page));
+        let title = info
+            .and_then(|info| info.title.clone())
+            .unwrap_or_else(|| resource_display_name(page).to_owned());
+        match info.and_then(|info| info.icon.as_ref()) {
+            Some(icon) => format!("{icon} {title}"),
+            None => title,
+        }
+    }
+
+    pub fn is_checklist_item_checked(&self, page: &str, item_id: &str, default: bool) -> bool {
+        self.checklist_state
+            .borrow()
+            .get(page)
+            .and_then(|items| items.get(item_id))
+            .copied()
+            .unwrap_or(default)
+    }
+
+    // called while rendering, hence the RefCell
+    pub fn set_checklist_item(&self, page: &str, item_id: &str, checked: bool) {
+        self.checklist_state
+            .borrow_mut()
+            .entry(page.to_owned())
+            .or_default()
+            .insert(item_id.to_owned(), checked);
+    }
+
+    /// (checked, total) items of the current page, None if it has no checklist
+    pub fn get_checklist_progress(&self) -> Option<(usize, usize)> {
+        let page = self.localized_key(self.current_resource.as_ref()?);
+        let items = self.checklist_items.get(&page)?;
+        let checked = items
+            .iter()
+            .filter(|(id, default)| self.is_checklist_item_checked(&page, id, *default))
+            .count();
+        Some((checked, items.len()))
+    }
+
+    /// forgets everything the user ticked on the current page
+    pub fn reset_current_checklist(&mut self) {
+        if let Some(current) = &self.current_resource {
+            let page = self.localized_key(current);
+            self.checklist_state.get_mut().remove(&page);
+        }
+    }
+
+    pub fn get_checklist_state(&self) -> BTreeMap<String, BTreeMap<String, bool>> {
+        self.checklist_state.borrow().clone()
+    }
+
+    // items that aren't in the files anymore are kept, they might come back with the next edit
+    pub fn restore_checklist_state(&mut self, state: BTreeMap<String, BTreeMap<String, bool>>) {
+        *self.checklist_state.get_mut() = state;
+    }
+
+    pub fn get_current_table_of_contents(&self) -> Vec<TocEntry> {
+        self.get_current_resource()
+            .map(|res| res.get_table_of_contents())
+            .unwrap_or_default()
     }
 
     pub fn get_resource(&self, key: impl Into<String>) -> Option<&dyn Resource> {
@@ -303,7 +903,33 @@ impl RessourcesSubsystem {
     pub fn get_current_resource(&self) -> Option<&dyn Resource> {
         self.current_resource
             .as_ref()
-            .and_then(|key| self.get_resource(key))
+            .and_then(|key| self.get_resource(self.localized_key(key)))
+    }
+
+    /// key of the translated variant of a page in the display locale, if there is one
+    fn localized_key(&self, page: &str) -> String {
+        match self.localized_pages.get(&self.display_locale) {
+            Some(pages) if pages.contains(page) => format!("{}/{page}", self.display_locale),
+            _ => page.to_owned(),
+        }
+    }
+
+    /// translator report: pages of the default resources without a translation, per locale
+    pub fn get_missing_translations(&self, locale_keys: &[String]) -> Vec<(String, Vec<String>)> {
+        locale_keys
+            .iter()
+            .filter(|locale| locale.as_str() != DEFAULT_UI_LOCALE) // default resources are english
+            .map(|locale| {
+                let translated = self.localized_pages.get(locale);
+                let missing = self
+                    .translatable_pages
+                    .iter()
+                    .filter(|page| translated.is_none_or(|t| !t.contains(*page)))
+                    .cloned()
+                    .collect();
+                (locale.clone(), missing)
+            })
+            .collect()
     }
 
     pub fn render_current_resource(&mut self, ui: &mut Ui) {
@@ -322,6 +948,11 @@ impl RessourcesSubsystem {
 pub trait Resource {
     fn get_title(&self) -> String;
 
+    /// headings of this resource, only pages (collections) have one
+    fn get_table_of_contents(&self) -> Vec<TocEntry> {
+        Vec::new()
+    }
+
     // renders the resource to the provided Ui and returns an optional new current resource
     fn render_resource(
         &self,
@@ -339,13 +970,20 @@ pub struct ResourceCollection {
     resource_title: String,
 
     resource_list: Vec<String>,
+
+    table_of_contents: Vec<TocEntry>,
 }
 
 impl ResourceCollection {
-    pub fn new(resource_title: impl Into<String>, ordered_res_keys: Vec<String>) -> Self {
+    pub fn new(
+        resource_title: impl Into<String>,
+        ordered_res_keys: Vec<String>,
+        table_of_contents: Vec<TocEntry>,
+    ) -> Self {
         Self {
             resource_title: resource_title.into(),
             resource_list: ordered_res_keys,
+            table_of_contents,
         }
     }
 }
@@ -355,6 +993,10 @@ impl Resource for ResourceCollection {
         self.resource_title.clone()
     }
 
+    fn get_table_of_contents(&self) -> Vec<TocEntry> {
+        self.table_of_contents.clone()
+    }
+
     fn render_resource(
         &self,
         resource_subsystem: &RessourcesSubsystem,
@@ -380,13 +1022,15 @@ impl Resource for ResourceCollection {
 /// Web Link Resource
 //////////////////////////////////
 pub struct WebLinkResource {
+    resource_key: String,
     link_text: String,
     link_url: String,
 }
 
 impl WebLinkResource {
-    fn new(text: impl Into<String>, url: impl Into<String>) -> Self {
+    fn new(key: impl Into<String>, text: impl Into<String>, url: impl Into<String>) -> Self {
         Self {
+            resource_key: key.into(),
             link_text: text.into(),
             link_url: url.into(),
         }
@@ -395,7 +1039,7 @@ impl WebLinkResource {
 
 impl Resource for WebLinkResource {
     fn get_title(&self) -> String {
-        self.link_text.clone()
+        self.resource_key.clone()
     }
 
     fn render_resource(
@@ -412,14 +1056,16 @@ impl Resource for WebLinkResource {
 /// In App Link to another resource
 //////////////////////////////////
 pub struct AppLinkResource {
-    link_text: String,
+    resource_key: String,
+    link_text: Option<String>, // None -> title of the linked page
     link_to: String,
 }
 
 impl AppLinkResource {
-    fn new(resource_title: impl Into<String>, link_to: impl Into<String>) -> Self {
+    fn new(key: impl Into<String>, link_text: Option<String>, link_to: impl Into<String>) -> Self {
         Self {
-            link_text: resource_title.into(),
+            resource_key: key.into(),
+            link_text,
             link_to: link_to.into(),
         }
     }
@@ -427,14 +1073,22 @@ impl AppLinkResource {
 
 impl Resource for AppLinkResource {
     fn get_title(&self) -> String {
-        self.link_text.clone()
+        self.resource_key.clone()
     }
 
     fn render_resource(
         &self,
-        _resource_subsystem: &RessourcesSubsystem,
+        resource_subsystem: &RessourcesSubsystem,
         ui: &mut Ui,
     ) -> Option<String> {
+        let text = match &self.link_text {
+            Some(text) => text.clone(),
+            None => match split_anchor(&self.link_to) {
+                ("", _) => self.link_to.clone(), // "#heading" in the same page
+                (page, _) => resource_subsystem.get_page_title(page),
+            },
+        };
+
         let mut link_to = None;
         ui.horizontal(|ui| {
             // setup custom interactable widget style, because buttons and selectable labels share a style
@@ -445,9 +1099,6 @@ impl Resource for AppLinkResource {
             style.spacing.item_spacing = Vec2::new(3., style.spacing.item_spacing.y);
 
             // actual rendering
-            let text = self.link_text.trim_end_matches("_AppLink"); // meh ... I kinda wish I
-            // hadn't double used the link_text as resource key and display text right now.
-
             let title = Label::new(text).sense(Sense::click());
             let response = ui.add(title);
 
@@ -471,6 +1122,115 @@ impl Resource for AppLinkResource {
     }
 }
 
+////////////////////////////////////////////
+/// Heading Resource
+////////////////////////////////////////////
+/// a single markdown heading, which can be targeted by "Page#anchor" links
+pub struct HeadingResource {
+    resource_title: String,
+    anchor: String,
+    markdown: MarkdownResource,
+}
+
+impl HeadingResource {
+    pub fn new(
+        resource_title: impl Into<String>,
+        level: usize,
+        heading_text: impl Into<String>,
+        anchor: impl Into<String>,
+    ) -> Self {
+        let title: String = resource_title.into();
+        Self {
+            markdown: MarkdownResource::new_keeping_headings(
+                &title,
+                heading_markdown(level, &heading_text.into()),
+            ),
+            resource_title: title,
+            anchor: anchor.into(),
+        }
+    }
+}
+
+impl Resource for HeadingResource {
+    fn get_title(&self) -> String {
+        self.resource_title.clone()
+    }
+
+    fn render_resource(
+        &self,
+        resource_subsystem: &RessourcesSubsystem,
+        ui: &mut Ui,
+    ) -> Option<String> {
+        let response = ui.scope(|ui| self.markdown.render_resource(resource_subsystem, ui));
+
+        if resource_subsystem.take_pending_scroll_anchor(&self.anchor) {
+            ui.scroll_to_rect(response.response.rect, Some(Align::TOP));
+        }
+        response.inner
+    }
+}
+
+////////////////////////////////////////////
+/// Checklist Item Resource
+////////////////////////////////////////////
+/// a "- [ ] step" line, its state is remembered per page & item text (see SaveState)
+pub struct ChecklistItemResource {
+    resource_title: String,
+    page: String,
+    item_id: String,
+    checked_by_default: bool,
+    markdown: MarkdownResource,
+}
+
+impl ChecklistItemResource {
+    pub fn new(
+        resource_title: impl Into<String>,
+        page: impl Into<String>,
+        text: impl Into<String>,
+        item_id: impl Into<String>,
+        checked_by_default: bool,
+    ) -> Self {
+        let title: String = resource_title.into();
+        Self {
+            markdown: MarkdownResource::new(&title, text),
+            resource_title: title,
+            page: page.into(),
+            item_id: item_id.into(),
+            checked_by_default,
+        }
+    }
+}
+
+impl Resource for ChecklistItemResource {
+    fn get_title(&self) -> String {
+        self.resource_title.clone()
+    }
+
+    fn render_resource(
+        &self,
+        resource_subsystem: &RessourcesSubsystem,
+        ui: &mut Ui,
+    ) -> Option<String> {
+        let mut checked = resource_subsystem.is_checklist_item_checked(
+            &self.page,
+            &self.item_id,
+            self.checked_by_default,
+        );
+
+        ui.horizontal(|ui| {
+            if ui.checkbox(&mut checked, "").changed() {
+                resource_subsystem.set_checklist_item(&self.page, &self.item_id, checked);
+            }
+            // done steps fade out a bit
+            if checked {
+                ui.set_opacity(0.5);
+            }
+            self.markdown.render_resource(resource_subsystem, ui)
+        })
+        .inner
+    }
+}
+
 ////////////////////////////////////////////
 /// Markdown Resource
 ////////////////////////////////////////////
@@ -491,11 +1251,7 @@ impl MarkdownResource {
         // normalizes all headings to level 5, because it's the first non-strong but still enlarged heading
         let adjusted_md_text = re.replace_all(md_text.as_str(), "##### ").into_owned();
 
-        Self {
-            resource_title: resource_title.into(),
-            markdown_text: adjusted_md_text,
-            markdown_cache: RefCell::new(CommonMarkCache::default()),
-        }
+        Self::new_keeping_headings(resource_title, adjusted_md_text)
         // DEBUG
         // let title: String = resource_title.into();
         // Self {
@@ -504,6 +1260,18 @@ impl MarkdownResource {
         //     markdown_cache: RefCell::new(CommonMarkCache::default()),
         // }
     }
+
+    /// like new, but the headings keep their level (page headings, whose level is the page's hierarchy)
+    pub fn new_keeping_headings(
+        resource_title: impl Into<String>,
+        markdown_text: impl Into<String>,
+    ) -> Self {
+        Self {
+            resource_title: resource_title.into(),
+            markdown_text: markdown_text.into(),
+            markdown_cache: RefCell::new(CommonMarkCache::default()),
+        }
+    }
 }
 
 impl Resource for MarkdownResource {
@@ -546,15 +1314,299 @@ impl Resource for MarkdownResource {
     }
 }
 
+/// splits "Page#heading" into its page key and optional anchor
+pub fn split_anchor(link: &str) -> (&str, Option<&str>) {
+    match link.split_once(ANCHOR_SEPARATOR) {
+        Some((page, anchor)) => (page.trim(), Some(anchor.trim())),
+        None => (link.trim(), None),
+    }
+}
+
+// md-files of every installed resource pack, namespaced as "packs/<pack-id>/..."
+fn read_in_resource_packs() -> Vec<(String, String)> {
+    let Ok(packs_dir) = resource_packs::get_resource_packs_dir() else {
+        return Vec::new();
+    };
+
+    let mut md_file_list = Vec::new();
+    for pack in resource_packs::read_installed_packs(&packs_dir) {
+        match global_utils::read_in_all_markdown_files(packs_dir.join(&pack.id)) {
+            Ok(list) => {
+                md_file_list.extend(list.into_iter().map(|(key, content)| {
+                    (format!("{PACK_KEY_PREFIX}/{}/{key}", pack.id), content)
+                }))
+            }
+            Err(e) => eprintln!("ResourceSubsystem - couldn't read pack {}: {e}", pack.id),
+        }
+    }
+    md_file_list
+}
+
+// table files sit next to the md-file (for translations also next to the original page)
+// paths can't leave the resources folder (or the folder of the resource pack)
+fn find_table_file(page_key: &str, link_base: &str, file: &str) -> Result<PathBuf, String> {
+    let pack_prefix = format!("{PACK_KEY_PREFIX}{PATH_SEPARATOR}");
+    let (base_dir, page, original_page) = match page_key.strip_prefix(&pack_prefix) {
+        Some(pack_page) => {
+            let (pack_id, page) = pack_page
+                .split_once(PATH_SEPARATOR)
+                .unwrap_or(("", pack_page));
+            let packs_dir = resource_packs::get_resource_packs_dir().map_err(|e| e.to_string())?;
+            let original_page = link_base.strip_prefix(&pack_prefix).unwrap_or(link_base);
+            let original_page = original_page
+                .split_once(PATH_SEPARATOR)
+                .map(|(_, page)| page)
+                .unwrap_or(original_page);
+            (packs_dir.join(pack_id), page, original_page)
+        }
+        None => (
+            RessourcesSubsystem::resource_folder_path().map_err(|e| e.to_string())?,
+            page_key,
+            link_base,
+        ),
+    };
+
+    let candidates = [page, original_page].map(|page| {
+        // "/Tables/x.csv" is relative to the resources root, like AppLinks
+        let key = match file.starts_with(PATH_SEPARATOR) {
+            true => normalize_resource_path(file),
+            false => normalize_resource_path(&format!("{}/{file}", parent_folder(page))),
+        };
+        let mut path = base_dir.clone();
+        path.extend(key.split(PATH_SEPARATOR));
+        path
+    });
+    candidates
+        .iter()
+        .find(|path| path.is_file())
+        .cloned()
+        .ok_or_else(|| format!("\"{file}\" not found in {}", candidates[0].display()))
+}
+
+/// "DE/Guides/Ho-Oh-Guide" -> ("DE", "Guides/Ho-Oh-Guide") if "DE" is a known locale
+pub fn split_locale_prefix<'a>(key: &'a str, locale_keys: &[String]) -> Option<(&'a str, &'a str)> {
+    key.split_once(PATH_SEPARATOR)
+        .filter(|(locale, _)| locale_keys.iter().any(|l| l == locale))
+}
+
+/// "viridian forest" -> "Viridian Forest"
+pub fn capitalize_words(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// folder part of a path-qualified resource key ("" for top level resources)
+pub fn parent_folder(key: &str) -> &str {
+    key.rsplit_once(PATH_SEPARATOR)
+        .map(|(folder, _)| folder)
+        .unwrap_or("")
+}
+
+/// last segment of a path-qualified resource key, which is what users want to read
+pub fn resource_display_name(key: &str) -> &str {
+    key.rsplit_once(PATH_SEPARATOR)
+        .map(|(_, name)| name)
+        .unwrap_or(key)
+}
+
+/// lowercase tag/region without characters that have a meaning in resource keys
+fn index_key_segment(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .replace([PATH_SEPARATOR, ANCHOR_SEPARATOR], "-")
+}
+
+/// all page keys plus every folder (category) they are nested in
+pub fn collect_page_keys<'a>(file_keys: impl Iterator<Item = &'a str>) -> HashSet<String> {
+    let mut page_keys = HashSet::new();
+    for key in file_keys {
+        page_keys.insert(key.to_owned());
+        let mut folder = parent_folder(key);
+        while !folder.is_empty() {
+            page_keys.insert(folder.to_owned());
+            folder = parent_folder(folder);
+        }
+    }
+    page_keys
+}
+
+fn folders_contain(page_keys: &HashSet<String>, key: &str) -> bool {
+    page_keys.iter().any(|other| parent_folder(other) == key)
+}
+
+/// collapses "." and ".." segments and unifies separators
+pub fn normalize_resource_path(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split(['/', '\\']) {
+        match segment.trim() {
+            "" | "." => (),
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(s),
+        }
+    }
+    segments.join("/")
+}
+
+/// resolves the target of an AppLink written on the page `from_page`
+/// - "/Page" is always relative to the resources root
+/// - "Page", "./Page" or "../Page" are relative to the folder of `from_page`, if such a page exists,
+///   otherwise relative to the resources root (which keeps old flat links working)
+pub fn resolve_resource_link(from_page: &str, link: &str, page_keys: &HashSet<String>) -> String {
+    let (page, anchor) = split_anchor(link);
+
+    let resolved_page = if page.is_empty() {
+        String::new() // anchor into the same page
+    } else if let Some(absolute) = page.strip_prefix('/') {
+        normalize_resource_path(absolute)
+    } else {
+        let relative = normalize_resource_path(&format!("{}/{page}", parent_folder(from_page)));
+        if page_keys.contains(&relative) {
+            relative
+        } else {
+            normalize_resource_path(page)
+        }
+    };
+
+    match anchor {
+        Some(anchor) => format!("{resolved_page}{ANCHOR_SEPARATOR}{anchor}"),
+        None => resolved_page,
+    }
+}
+
+/// builds a github-like anchor out of a heading text, e.g. "Sp. Atk" -> "sp-atk"
+pub fn heading_anchor(heading_text: &str) -> String {
+    let mut anchor = String::with_capacity(heading_text.len());
+    for c in heading_text.chars() {
+        if c.is_alphanumeric() {
+            anchor.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-') && !anchor.is_empty() && !anchor.ends_with('-') {
+            anchor.push('-');
+        }
+    }
+    anchor.trim_end_matches('-').to_owned()
+}
+
+/// the markdown of a page heading, e.g. "### Text" for level 3
+pub fn heading_markdown(level: usize, text: &str) -> String {
+    format!("{} {text}", "#".repeat(level.clamp(1, 6)))
+}
+
+/// anchors have to be unique per page, so repeated headings get a counter appended
+pub fn unique_anchor(anchor: &str, existing: &[TocEntry]) -> String {
+    let mut candidate = anchor.to_owned();
+    let mut counter = 1;
+    while existing.iter().any(|entry| entry.anchor == candidate) {
+        candidate = format!("{anchor}-{counter}");
+        counter += 1;
+    }
+    candidate
+}
+
 /////////////////////////////////////////////
 ///  Tests
 /////////////////////////////////////////////
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
 
     #[test]
     fn test_something() {
         assert!(true == true);
     }
+
+    #[test]
+    fn test_heading_anchor() {
+        assert_eq!(heading_anchor("Battle Walkthrough"), "battle-walkthrough");
+        assert_eq!(heading_anchor("Sp. Atk"), "sp-atk");
+        assert_eq!(heading_anchor("**Notes:**"), "notes");
+        assert_eq!(heading_anchor("Route 1 - Kanto"), "route-1-kanto");
+    }
+
+    #[test]
+    fn test_heading_markdown() {
+        assert_eq!(heading_markdown(1, "Johto"), "# Johto");
+        assert_eq!(heading_markdown(3, "Gym 1"), "### Gym 1");
+        assert_eq!(heading_markdown(9, "Deep"), "###### Deep");
+    }
+
+    #[test]
+    fn test_split_anchor() {
+        assert_eq!(
+            split_anchor("Ho-Oh-Guide#notes"),
+            ("Ho-Oh-Guide", Some("notes"))
+        );
+        assert_eq!(split_anchor("#notes"), ("", Some("notes")));
+        assert_eq!(split_anchor("ROOT"), ("ROOT", None));
+    }
+
+    #[test]
+    fn test_resolve_resource_link() {
+        let page_keys = collect_page_keys(
+            [
+                "ROOT",
+                "EV Hordes",
+                "Guides/Ho-Oh-Guide",
+                "Guides/Ho-Oh-Team",
+            ]
+            .into_iter(),
+        );
+        assert!(page_keys.contains("Guides"));
+
+        let resolve = |from, link| resolve_resource_link(from, link, &page_keys);
+        assert_eq!(
+            resolve("Guides/Ho-Oh-Guide", "Ho-Oh-Team"),
+            "Guides/Ho-Oh-Team"
+        );
+        assert_eq!(
+            resolve("Guides/Ho-Oh-Guide", "./Ho-Oh-Team#moves"),
+            "Guides/Ho-Oh-Team#moves"
+        );
+        assert_eq!(resolve("Guides/Ho-Oh-Guide", "EV Hordes"), "EV Hordes");
+        assert_eq!(resolve("Guides/Ho-Oh-Guide", "../ROOT"), "ROOT");
+        assert_eq!(resolve("ROOT", "Guides/Ho-Oh-Guide"), "Guides/Ho-Oh-Guide");
+        assert_eq!(resolve("ROOT", "/Guides/Ho-Oh-Team"), "Guides/Ho-Oh-Team");
+        assert_eq!(resolve("ROOT", "#money"), "#money");
+    }
+
+    #[test]
+    fn test_split_locale_prefix() {
+        let locale_keys = ["DE".to_owned(), "FR".to_owned()];
+        assert_eq!(
+            split_locale_prefix("DE/Guides/Ho-Oh-Guide", &locale_keys),
+            Some(("DE", "Guides/Ho-Oh-Guide"))
+        );
+        assert_eq!(
+            split_locale_prefix("Guides/Ho-Oh-Guide", &locale_keys),
+            None
+        );
+        assert_eq!(split_locale_prefix("DE", &locale_keys), None);
+    }
+
+    #[test]
+    fn test_capitalize_words() {
+        assert_eq!(capitalize_words("viridian forest"), "Viridian Forest");
+        assert_eq!(capitalize_words("ho-oh"), "Ho-oh");
+        assert_eq!(capitalize_words(""), "");
+    }
+
+    #[test]
+    fn test_unique_anchor() {
+        let existing = vec![TocEntry {
+            level: 3,
+            text: "HP".into(),
+            anchor: "hp".into(),
+        }];
+        assert_eq!(unique_anchor("hp", &existing), "hp-1");
+        assert_eq!(unique_anchor("atk", &existing), "atk");
+    }
 }
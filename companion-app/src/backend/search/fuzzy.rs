@@ -0,0 +1,120 @@
+// typo tolerant matching of a search prompt against a text, both are expected to be normalized
+// already (lowercase, no accents, words separated by a single space)
+
+/// how well a text matches a prompt, sorts from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchQuality {
+    Exact,        // the text is the prompt
+    Prefix,       // the text starts with the prompt
+    WordPrefix,   // every prompt word starts a word of the text, "punch fir" -> "fire punch"
+    Fuzzy(usize), // like WordPrefix, but with typos (how many)
+}
+
+/// None if any word of the prompt has no (close enough) match in the text
+pub fn match_quality(prompt: &str, text: &str) -> Option<MatchQuality> {
+    if text == prompt {
+        return Some(MatchQuality::Exact);
+    }
+    if text.starts_with(prompt) {
+        return Some(MatchQuality::Prefix);
+    }
+
+    let mut typos = 0;
+    for token in prompt.split(' ') {
+        typos += text
+            .split(' ')
+            .filter_map(|word| word_typos(token, word))
+            .min()?;
+    }
+    match typos {
+        0 => Some(MatchQuality::WordPrefix),
+        n => Some(MatchQuality::Fuzzy(n)),
+    }
+}
+
+// longer words may have more typos, short ones have to be typed right
+fn allowed_typos(token_len: usize) -> usize {
+    match token_len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+// typos needed to turn `token` into the start of `word` (the user might not be done typing)
+fn word_typos(token: &str, word: &str) -> Option<usize> {
+    if word.starts_with(token) {
+        return Some(0);
+    }
+    let allowed = allowed_typos(token.chars().count());
+    if allowed == 0 {
+        return None;
+    }
+
+    let token: Vec<char> = token.chars().collect();
+    let word: Vec<char> = word.chars().collect();
+    // a missing or extra letter shifts the end of the typed part
+    let min_len = token.len().saturating_sub(allowed);
+    let max_len = (token.len() + allowed).min(word.len());
+    (min_len..=max_len)
+        .map(|len| edit_distance(&token, &word[..len]))
+        .min()
+        .filter(|typos| *typos <= allowed)
+}
+
+/// optimal string alignment distance, a swap of two neighbouring letters counts as one typo
+pub fn edit_distance(a: &[char], b: &[char]) -> usize {
+    // rows of the dp table for the previous two and the current letter of `a`
+    let mut before_last: Vec<usize> = vec![0; b.len() + 1];
+    let mut last: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (last[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(last[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_last[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before_last, &mut last);
+        std::mem::swap(&mut last, &mut current);
+    }
+    last[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_quality() {
+        assert_eq!(
+            match_quality("garchomp", "garchomp"),
+            Some(MatchQuality::Exact)
+        );
+        assert_eq!(
+            match_quality("fire", "fire punch"),
+            Some(MatchQuality::Prefix)
+        );
+        assert_eq!(
+            match_quality("punch fir", "fire punch"),
+            Some(MatchQuality::WordPrefix)
+        );
+        assert_eq!(
+            match_quality("garchmop", "garchomp"),
+            Some(MatchQuality::Fuzzy(1))
+        );
+        // still typing
+        assert_eq!(
+            match_quality("grach", "garchomp"),
+            Some(MatchQuality::Fuzzy(1))
+        );
+        assert_eq!(match_quality("punch ice", "fire punch"), None);
+        assert_eq!(match_quality("gx", "garchomp"), None);
+        assert!(MatchQuality::Prefix < MatchQuality::Fuzzy(1));
+    }
+}
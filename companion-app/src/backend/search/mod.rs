@@ -1,3 +1,4 @@
 pub mod data_bridge;
+pub mod fuzzy;
 pub mod search_index;
 pub mod search_update;
@@ -1,9 +1,18 @@
+use super::network::NetworkMode;
+
+pub const DEFAULT_UI_LOCALE: &str = "EN";
+
 /// runtime value store for all settings and container for settings behaviour
 pub struct SettingsSubsystem {
     pub version: u8,
     pub disable_overlay: bool,
     pub transparent_background_always: bool,
     pub type_matrix_scale: f32,
+    pub ui_locale: String, // locale key (e.g. "DE") data-bound content is displayed in
+    pub resource_pack_url: String, // there is no public pack server yet, empty until the user sets one
+    pub network_mode: NetworkMode,
+    pub mirror_url: String, // replaces the default data url everywhere, empty -> no mirror
+    pub clipboard_translation: bool, // copied text is looked up in the Language Helper
 
     // transient request flags
     pub request_viewport_restart: bool,
@@ -28,6 +37,11 @@ impl SettingsSubsystem {
             disable_overlay: false,
             transparent_background_always: false,
             type_matrix_scale: 1.0,
+            ui_locale: DEFAULT_UI_LOCALE.to_string(),
+            resource_pack_url: String::new(),
+            network_mode: NetworkMode::default(),
+            mirror_url: String::new(),
+            clipboard_translation: false,
             request_viewport_restart: false,
             request_clear_ui_data: false,
             _dev_sliders: dev_sliders,
@@ -1,10 +1,16 @@
 // has functions for reading/writing JSON (or syncing with cloud?)
 
-use std::{fs, io, path::PathBuf};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::app::OverlayApp;
+use crate::{
+    app::OverlayApp,
+    backend::{
+        language_helper::locale_data_bridge::DEFAULT_SEARCH_CATEGORIES, locales::TextCategory,
+        network::NetworkMode, settings::DEFAULT_UI_LOCALE,
+    },
+};
 
 /////////////////////////////////////////////////////////////////////
 // Save State
@@ -20,6 +26,19 @@ pub struct SaveState {
     type_matrix_scale: f32,
     language_helper_source: String,
     language_helper_target: String,
+    language_helper_categories: Vec<TextCategory>,
+    language_helper_compare_all: bool,
+    ui_locale: String,
+    resource_pack_url: String,
+    network_mode: NetworkMode,
+    mirror_url: String,
+    clipboard_translation: bool,
+    resources_current: String,
+    resources_back_history: Vec<String>,
+    resources_forward_history: Vec<String>,
+    resources_bookmarks: Vec<String>,
+    resources_recent: Vec<String>,
+    resources_checklists: BTreeMap<String, BTreeMap<String, bool>>, // page -> item -> checked
 }
 
 // default save values
@@ -33,6 +52,19 @@ impl Default for SaveState {
             type_matrix_scale: 1.0,
             language_helper_source: "".to_string(),
             language_helper_target: "".to_string(),
+            language_helper_categories: DEFAULT_SEARCH_CATEGORIES.to_vec(),
+            language_helper_compare_all: false,
+            ui_locale: DEFAULT_UI_LOCALE.to_string(),
+            resource_pack_url: "".to_string(),
+            network_mode: NetworkMode::default(),
+            mirror_url: "".to_string(),
+            clipboard_translation: false,
+            resources_current: "".to_string(),
+            resources_back_history: Vec::new(),
+            resources_forward_history: Vec::new(),
+            resources_bookmarks: Vec::new(),
+            resources_recent: Vec::new(),
+            resources_checklists: BTreeMap::new(),
         }
     }
 }
@@ -42,6 +74,8 @@ impl Default for SaveState {
 // used to create/derive a SaveState from a full OverlayApp object
 impl From<&OverlayApp> for SaveState {
     fn from(app: &OverlayApp) -> Self {
+        let (resources_current, resources_back_history, resources_forward_history) =
+            app.ressources.get_history();
         SaveState {
             version: app.settings.version,
             disable_overlay: app.settings.disable_overlay,
@@ -50,6 +84,19 @@ impl From<&OverlayApp> for SaveState {
             type_matrix_scale: app.settings.type_matrix_scale,
             language_helper_source: app.language_helper.get_translation_source_locale().clone(),
             language_helper_target: app.language_helper.get_translation_target_locale().clone(),
+            language_helper_categories: app.language_helper.get_searched_categories(),
+            language_helper_compare_all: app.language_helper.is_comparing_all_locales(),
+            ui_locale: app.settings.ui_locale.clone(),
+            resource_pack_url: app.settings.resource_pack_url.clone(),
+            network_mode: app.settings.network_mode,
+            mirror_url: app.settings.mirror_url.clone(),
+            clipboard_translation: app.settings.clipboard_translation,
+            resources_current,
+            resources_back_history,
+            resources_forward_history,
+            resources_bookmarks: app.ressources.get_bookmarks().to_vec(),
+            resources_recent: app.ressources.get_recent_resources().to_vec(),
+            resources_checklists: app.ressources.get_checklist_state(),
         }
     }
 }
@@ -65,6 +112,24 @@ pub fn push_save_state_into_app(save_state: SaveState, app: &mut OverlayApp) {
         .set_translation_target_locale(save_state.language_helper_target);
     app.language_helper
         .set_translation_source_locale(save_state.language_helper_source);
+    app.language_helper
+        .set_searched_categories(&save_state.language_helper_categories);
+    app.language_helper
+        .set_comparing_all_locales(save_state.language_helper_compare_all);
+    app.settings.ui_locale = save_state.ui_locale;
+    app.settings.resource_pack_url = save_state.resource_pack_url;
+    app.settings.network_mode = save_state.network_mode;
+    app.settings.mirror_url = save_state.mirror_url;
+    app.settings.clipboard_translation = save_state.clipboard_translation;
+    app.ressources.restore_history(
+        save_state.resources_current,
+        save_state.resources_back_history,
+        save_state.resources_forward_history,
+        save_state.resources_bookmarks,
+        save_state.resources_recent,
+    );
+    app.ressources
+        .restore_checklist_state(save_state.resources_checklists);
 }
 
 /////////////////////////////////////////////////////////////////////
@@ -0,0 +1,84 @@
+// helpers shared by the backend tests
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// minimal stand-in for a file host (locales, resource packs), serves fixed paths, 404s everything
+/// else and remembers every requested path
+pub fn serve<K: Into<String>, V: Into<String>>(
+    files: HashMap<K, V>,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    serve_with(files, false)
+}
+
+/// like serve, but the first response of every path breaks off in the middle of the body
+pub fn serve_flaky<K: Into<String>, V: Into<String>>(
+    files: HashMap<K, V>,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    serve_with(files, true)
+}
+
+fn serve_with<K: Into<String>, V: Into<String>>(
+    files: HashMap<K, V>,
+    break_off_first: bool,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    let files: HashMap<String, String> = files
+        .into_iter()
+        .map(|(path, body)| (path.into(), body.into()))
+        .collect();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let requests_ref = requests.clone();
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request_line = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            reader.read_line(&mut request_line).unwrap();
+            // drain headers
+            let mut header = String::new();
+            while reader.read_line(&mut header).is_ok_and(|n| n > 2) {
+                header.clear();
+            }
+
+            let path = request_line.split(' ').nth(1).unwrap_or("/").to_owned();
+            let mut requests = requests_ref.lock().unwrap();
+            let break_off = break_off_first && !requests.contains(&path);
+            let response = match files.get(&path) {
+                // announces the whole body, but closes the connection after half of it
+                Some(body) if break_off => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    &body[..body.len() / 2]
+                ),
+                Some(body) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                ),
+                None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_owned(),
+            };
+            requests.push(path);
+            drop(requests);
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    (base_url, requests)
+}
+
+/// empty directory in the system's temp dir, unique per test `name` and test run
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pokemmo_companion_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,131 @@
+// translations of the app's own texts (window titles, buttons, settings ...), looked up with tr!
+// every locale dir can have a ui.json: english text -> translation, everything missing stays english
+// loaded synchronously (it's small), so even texts before the locale initialization are translated
+
+use crate::utils::find_asset_folder;
+use std::{collections::HashMap, fmt::Display, fs, io, sync::RwLock};
+
+pub const UI_STRINGS_FILE: &str = "ui.json";
+
+struct UiStrings {
+    locale_key: String,
+    version: usize, // the locale init counter it was loaded at, a locale update reloads it
+    texts: HashMap<String, String>,
+}
+
+// global, so every draw function can translate without passing anything around
+static UI_STRINGS: RwLock<Option<UiStrings>> = RwLock::new(None);
+
+/// the UI language, only reads ui.json when the locale or its version changed
+pub fn set_ui_locale(locale_key: &str, version: usize) {
+    if let Some(current) = &*UI_STRINGS.read().unwrap()
+        && current.locale_key == locale_key
+        && current.version == version
+    {
+        return;
+    }
+
+    let texts = load_ui_strings(locale_key).unwrap_or_else(|e| {
+        // e.g. english, which doesn't need one
+        if e.kind() != io::ErrorKind::NotFound {
+            eprintln!("UiStrings - couldn't load the {locale_key} UI texts, because {e}");
+        }
+        HashMap::new()
+    });
+    *UI_STRINGS.write().unwrap() = Some(UiStrings {
+        locale_key: locale_key.to_owned(),
+        version,
+        texts,
+    });
+}
+
+fn load_ui_strings(locale_key: &str) -> io::Result<HashMap<String, String>> {
+    let path = find_asset_folder()?
+        .join("locales")
+        .join(locale_key)
+        .join(UI_STRINGS_FILE);
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// the translation of `text` in the UI language, `text` itself if there is none
+pub fn translate(text: &str) -> String {
+    UI_STRINGS
+        .read()
+        .unwrap()
+        .as_ref()
+        .and_then(|strings| strings.texts.get(text))
+        .filter(|translation| !translation.is_empty())
+        .map_or_else(|| text.to_owned(), String::clone)
+}
+
+/// like translate, then every "{}" is replaced by the next argument
+pub fn translate_args(text: &str, args: &[&dyn Display]) -> String {
+    fill_placeholders(&translate(text), args)
+}
+
+fn fill_placeholders(template: &str, args: &[&dyn Display]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut parts = template.split("{}").peekable();
+    while let Some(part) = parts.next() {
+        result.push_str(part);
+        if parts.peek().is_some() {
+            match args.next() {
+                Some(arg) => result.push_str(&arg.to_string()),
+                None => result.push_str("{}"), // a broken translation shouldn't lose the text
+            }
+        }
+    }
+    result
+}
+
+/// translates an english UI text: tr!("Settings") or with arguments tr!("{} files", count)
+#[macro_export]
+macro_rules! tr {
+    ($text:expr) => {
+        $crate::backend::ui_strings::translate($text)
+    };
+    ($text:expr, $($arg:expr),+ $(,)?) => {
+        $crate::backend::ui_strings::translate_args(
+            $text,
+            &[$(&$arg as &dyn ::std::fmt::Display),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_fill_placeholders() {
+        assert_eq!(
+            fill_placeholders("{} von {} Dateien", &[&3, &"7"]),
+            "3 von 7 Dateien"
+        );
+        assert_eq!(fill_placeholders("{} und {}", &[&1]), "1 und {}");
+        assert_eq!(fill_placeholders("keine", &[&1]), "keine");
+    }
+
+    #[test]
+    fn test_shipped_ui_strings() {
+        // a translation with other placeholders than its english text would mix up the arguments
+        let locales_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/locales");
+        for entry in fs::read_dir(locales_dir).unwrap().flatten() {
+            let path = entry.path().join(UI_STRINGS_FILE);
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let texts: HashMap<String, String> = serde_json::from_str(&content)
+                .unwrap_or_else(|e| panic!("{path:?} is broken: {e}"));
+            for (english, translation) in texts {
+                assert_eq!(
+                    english.matches("{}").count(),
+                    translation.matches("{}").count(),
+                    "{path:?}: \"{translation}\" doesn't fit \"{english}\""
+                );
+            }
+        }
+    }
+}
@@ -1,16 +1,25 @@
 use crate::{
     app::OverlayApp,
     backend::{
-        feature_state::Feature, language_helper::language_helper_feature::LanguageHelperSubsystem,
+        feature_state::Feature,
+        language_helper::{
+            language_helper_feature::{LanguageHelperSubsystem, TranslationPair},
+            locale_data_bridge::ANY_LOCALE,
+            phrase_translator::PhraseSegment,
+        },
+        locales::TextCategory,
     },
     frontend::{gui_subsystem::GuiSubsystem, style, utils::construct_base_window},
+    tr,
 };
 use egui::{
-    Color32, ComboBox, CornerRadius, Frame, Image, ImageButton, Label, Layout, Margin, ScrollArea,
-    Stroke, Vec2,
+    Align, Button, CollapsingHeader, Color32, ComboBox, CornerRadius, Frame, Image, ImageButton,
+    Key, Label, Layout, Margin, RichText, ScrollArea, Sense, Stroke, TopBottomPanel, Vec2,
 };
 use egui_extras::{Size, StripBuilder};
 
+const ANY_LOCALE_DISPLAY_NAME: &str = "🌐 Any Language";
+
 ////////////////////////////////////////////////////////////////////////////
 ///  LanguageHelper
 ////////////////////////////////////////////////////////////////////////////
@@ -30,6 +39,10 @@ pub fn draw_language_helper_panel(ctx: &egui::Context, state: &mut OverlayApp) {
 
             create_searchbar(&mut state.language_helper, ui);
 
+            create_copy_translation_bar(&mut state.language_helper, ui);
+
+            create_phrase_translator(&mut state.language_helper, ui);
+
             ui.separator();
 
             create_translation_list(&mut state.language_helper, &state.gui, ui);
@@ -60,6 +73,13 @@ fn create_locale_select_bar(
     }
 
     let mut swap_clicked = false;
+    let searching_any_locale = language_helper.is_searching_any_locale();
+    let source_display_name = match searching_any_locale {
+        true => tr!(ANY_LOCALE_DISPLAY_NAME),
+        false => language_helper
+            .locale_subsystem
+            .get_locale_display_name(&locale_source_selected),
+    };
 
     // build ui
     ui.scope(|ui| {
@@ -76,13 +96,15 @@ fn create_locale_select_bar(
                     ComboBox::from_id_salt("Translation_Source_Combobox")
                         .width(ui.available_width())
                         .truncate()
-                        .selected_text(
-                            language_helper
-                                .locale_subsystem
-                                .get_locale_display_name(&locale_source_selected)
-                                .to_string(),
-                        )
+                        .selected_text(source_display_name)
                         .show_ui(ui, |ui| {
+                            // only as source, there is no "any language" to translate into
+                            ui.selectable_value(
+                                &mut locale_source_selected,
+                                ANY_LOCALE.to_owned(),
+                                tr!(ANY_LOCALE_DISPLAY_NAME),
+                            )
+                            .on_hover_text(tr!("find words of every language at once"));
                             for i in 0..available_keys.len() {
                                 ui.selectable_value(
                                     &mut locale_source_selected,
@@ -96,7 +118,10 @@ fn create_locale_select_bar(
                 strip.cell(|ui| {
                     let swap_ui_image = Image::new(swap_image);
                     swap_clicked = ui
-                        .add(ImageButton::new(swap_ui_image).corner_radius(select_bar_height / 2.))
+                        .add_enabled(
+                            !searching_any_locale,
+                            ImageButton::new(swap_ui_image).corner_radius(select_bar_height / 2.),
+                        )
                         .clicked();
                 });
 
@@ -137,16 +162,52 @@ fn create_translation_list(
     ui: &mut egui::Ui,
 ) {
     let row_height: f32 = 30.0;
+    let locale_line_height: f32 = 20.0;
 
     let translation_pairs = language_helper.get_translation_pairs_for_search();
 
+    // in "compare all" mode every hit lists all locales, so all hits still have the same height
+    let compare_all = language_helper.is_comparing_all_locales();
+    let item_height = match compare_all {
+        true => {
+            let locale_count = language_helper.locale_subsystem.get_defined_locales().len();
+            let line_height = locale_line_height + ui.spacing().item_spacing.y;
+            row_height + locale_count as f32 * line_height
+        }
+        false => row_height,
+    };
+
+    // descriptions don't fit into a row, the clicked one is shown in full below the list
+    let selected_text_id = egui::Id::new("language_helper_selected_text");
+    let mut selected_text: Option<String> = ui.memory(|r| r.data.get_temp(selected_text_id));
+    let selected_pair = selected_text.as_ref().and_then(|selected| {
+        translation_pairs
+            .iter()
+            .find(|pair| pair.category.is_long_text() && pair.source_text == *selected)
+    });
+
+    TopBottomPanel::bottom("language_helper_long_text")
+        .resizable(true)
+        .show_animated_inside(ui, selected_pair.is_some(), |ui| {
+            let Some(pair) = selected_pair else {
+                return;
+            };
+            ScrollArea::vertical().max_height(200.).show(ui, |ui| {
+                // side by side, like the rows above
+                ui.columns(2, |columns| {
+                    columns[0].label(&pair.source_text);
+                    columns[1].label(&pair.translation);
+                });
+            });
+        });
+
     ScrollArea::vertical()
         .auto_shrink([false, false])
-        .show_rows(ui, row_height, translation_pairs.len(), |ui, range| {
+        .show_rows(ui, item_height, translation_pairs.len(), |ui, range| {
             // show_rows optimizes this ScrollArea so much, because only in range things are drawn
-            for (category, source_text, translation) in translation_pairs[range].iter() {
-                ui.scope(|ui| {
-                    ui.set_height(row_height);
+            for pair in translation_pairs[range].iter() {
+                let row_response = ui.scope(|ui| {
+                    ui.set_height(item_height);
                     Frame::new()
                         .corner_radius(4.0)
                         .stroke(Stroke {
@@ -154,43 +215,308 @@ fn create_translation_list(
                             color: Color32::WHITE,
                         })
                         .show(ui, |ui| {
-                            StripBuilder::new(ui)
-                                .size(Size::exact(row_height))
-                                .size(Size::remainder())
-                                .size(Size::remainder())
-                                .horizontal(|mut strip| {
-                                    strip.cell(|ui| {
-                                        let type_image = Image::new(
-                                            gui_subsystem.get_image_source(
-                                                format!("text_category_{:?}", category)
-                                                    .to_lowercase(),
-                                            ),
-                                        );
-                                        ui.centered_and_justified(|ui| {
-                                            ui.add_sized(Vec2::splat(row_height - 6.), type_image)
-                                                .on_hover_text(format!("Type: {:?}", category));
-                                        });
-                                    });
-
-                                    strip.cell(|ui| {
-                                        let loc_label = Label::new(source_text.clone()).truncate();
-                                        ui.add_sized(
-                                            Vec2::new(ui.available_width(), row_height),
-                                            loc_label,
-                                        );
-                                    });
-
-                                    strip.cell(|ui| {
-                                        let trans_label =
-                                            Label::new(translation.clone()).truncate();
-                                        ui.add_sized(
-                                            Vec2::new(ui.available_width(), row_height),
-                                            trans_label,
-                                        );
-                                    });
-                                });
+                            ui.allocate_ui(Vec2::new(ui.available_width(), row_height), |ui| {
+                                draw_translation_pair(
+                                    language_helper,
+                                    pair,
+                                    gui_subsystem,
+                                    row_height,
+                                    ui,
+                                );
+                            });
+                            if compare_all {
+                                draw_all_locale_translations(
+                                    language_helper,
+                                    pair.category,
+                                    &pair.key,
+                                    locale_line_height,
+                                    ui,
+                                );
+                            }
                         });
                 });
+
+                if pair.category.is_long_text() {
+                    let response = row_response
+                        .response
+                        .interact(Sense::click())
+                        .on_hover_text(tr!("click to show the full text"));
+                    if response.clicked() {
+                        selected_text = match selected_text.as_ref() == Some(&pair.source_text) {
+                            true => None,
+                            false => Some(pair.source_text.clone()),
+                        };
+                    }
+                }
+            }
+        });
+
+    ui.memory_mut(|w| match selected_text {
+        Some(text) => w.data.insert_temp(selected_text_id, text),
+        None => w.data.remove::<String>(selected_text_id),
+    });
+}
+
+// category icon | source text | translation
+fn draw_translation_pair(
+    language_helper: &LanguageHelperSubsystem,
+    pair: &TranslationPair,
+    gui_subsystem: &GuiSubsystem,
+    row_height: f32,
+    ui: &mut egui::Ui,
+) {
+    StripBuilder::new(ui)
+        .size(Size::exact(row_height))
+        .size(Size::remainder())
+        .size(Size::remainder())
+        .horizontal(|mut strip| {
+            strip.cell(|ui| {
+                let hover_text = tr!("Type: {}", tr!(pair.category.display_name()));
+                ui.centered_and_justified(|ui| {
+                    match category_icon(pair.category) {
+                        Some(icon) => ui.add_sized(
+                            Vec2::splat(row_height - 6.),
+                            Image::new(gui_subsystem.get_image_source(icon)),
+                        ),
+                        None => ui.label(RichText::new("💬").size(16.)),
+                    }
+                    .on_hover_text(hover_text);
+                });
+            });
+
+            strip.cell(|ui| {
+                // "any language" mode shows where the text came from
+                let source_text = match &pair.source_locale {
+                    Some(locale) => format!("{} ({locale})", pair.source_text),
+                    None => pair.source_text.clone(),
+                };
+                let loc_label = Label::new(source_text).truncate();
+                ui.add_sized(Vec2::new(ui.available_width(), row_height), loc_label);
+            });
+
+            strip.cell(|ui| {
+                let translation = match pair.overridden {
+                    true => format!("✏ {}", pair.translation),
+                    false => pair.translation.clone(),
+                };
+                let trans_label = Label::new(translation).truncate().sense(Sense::click());
+                ui.add_sized(Vec2::new(ui.available_width(), row_height), trans_label)
+                    .on_hover_text(tr!("right click to edit the translation"))
+                    .context_menu(|ui| edit_translation_menu(language_helper, pair, ui));
+            });
+        });
+}
+
+// fixes go into the user override file of the target locale, updates don't overwrite them
+fn edit_translation_menu(
+    language_helper: &LanguageHelperSubsystem,
+    pair: &TranslationPair,
+    ui: &mut egui::Ui,
+) {
+    let target_locale = language_helper.get_translation_target_locale();
+    let edit_id = egui::Id::new("language_helper_edit_translation")
+        .with(&pair.key)
+        .with(target_locale);
+    let mut text: String = ui
+        .memory(|r| r.data.get_temp(edit_id))
+        .unwrap_or_else(|| pair.translation.clone());
+
+    ui.label(tr!("Translation ({}) :", target_locale));
+    let response = ui.text_edit_singleline(&mut text);
+    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+
+    let mut close = false;
+    ui.horizontal(|ui| {
+        let text_valid = !text.trim().is_empty();
+        if ui
+            .add_enabled(text_valid, Button::new(tr!("Save")))
+            .clicked()
+            || (submitted && text_valid)
+        {
+            language_helper.set_translation_override(pair, Some(text.trim().to_owned()));
+            close = true;
+        }
+        if ui
+            .add_enabled(pair.overridden, Button::new(tr!("Reset")))
+            .on_hover_text(tr!("use the shipped translation again"))
+            .clicked()
+        {
+            language_helper.set_translation_override(pair, None);
+            close = true;
+        }
+    });
+
+    match close {
+        true => {
+            ui.memory_mut(|w| w.data.remove::<String>(edit_id));
+            ui.close_menu();
+        }
+        false => ui.memory_mut(|w| w.data.insert_temp(edit_id, text)),
+    }
+}
+
+// one line per locale with a copy button, so nobody has to switch the target locale around
+fn draw_all_locale_translations(
+    language_helper: &LanguageHelperSubsystem,
+    category: TextCategory,
+    key: &str,
+    line_height: f32,
+    ui: &mut egui::Ui,
+) {
+    for (locale_key, text) in language_helper.get_translations_in_all_locales(category, key) {
+        let line_size = Vec2::new(ui.available_width(), line_height);
+        ui.allocate_ui_with_layout(line_size, Layout::left_to_right(Align::Center), |ui| {
+            ui.add_sized(
+                Vec2::new(30., line_height),
+                Label::new(RichText::new(&locale_key).small().weak()),
+            )
+            .on_hover_text(
+                language_helper
+                    .locale_subsystem
+                    .get_locale_display_name(&locale_key),
+            );
+
+            let Some(text) = text else {
+                ui.label(RichText::new("—").weak())
+                    .on_hover_text(tr!("not loaded or not translated"));
+                return;
+            };
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ui
+                    .small_button("📋")
+                    .on_hover_text(tr!("copy \"{}\"", text))
+                    .clicked()
+                {
+                    language_helper.note_own_copy(&text);
+                    ui.ctx().copy_text(text.clone());
+                }
+                ui.add(Label::new(&text).truncate());
+            });
+        });
+    }
+}
+
+// some categories share an icon, misc texts don't have one
+fn category_icon(category: TextCategory) -> Option<&'static str> {
+    match category {
+        TextCategory::Monster => Some("text_category_monster"),
+        TextCategory::Move => Some("text_category_move"),
+        TextCategory::Location | TextCategory::PokedexLocation => Some("text_category_location"),
+        TextCategory::Item | TextCategory::ItemDescription => Some("text_category_item"),
+        TextCategory::Miscellaneous => None,
+    }
+}
+
+// one toggle per category, at least one has to stay searched
+fn create_category_filters(language_helper: &mut LanguageHelperSubsystem, ui: &mut egui::Ui) {
+    let searched_count = language_helper.get_searched_categories().len();
+
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 4.;
+        for category in TextCategory::ALL {
+            let searched = language_helper.is_category_searched(category);
+            let response = ui.selectable_label(
+                searched,
+                RichText::new(tr!(category.display_name())).small(),
+            );
+            if response.clicked() && !(searched && searched_count == 1) {
+                language_helper.set_category_searched(category, !searched);
+            }
+        }
+    });
+}
+
+// copies the best translation back, e.g. to answer in the chat
+fn create_copy_translation_bar(language_helper: &mut LanguageHelperSubsystem, ui: &mut egui::Ui) {
+    let has_top_translation = language_helper.get_top_translation().is_some();
+
+    ui.horizontal(|ui| {
+        let button = ui
+            .add_enabled(
+                has_top_translation,
+                Button::new(RichText::new(tr!("📋 Copy top translation")).small()),
+            )
+            .on_hover_text("Alt + Y");
+        // Alt+Y is a global hotkey, handled by the app even while the gui is hidden
+        if button.clicked() {
+            language_helper.copy_top_translation();
+        }
+
+        let mut compare_all = language_helper.is_comparing_all_locales();
+        ui.toggle_value(
+            &mut compare_all,
+            RichText::new(tr!("🌐 Compare all")).small(),
+        )
+        .on_hover_text(tr!("show every hit in all languages"));
+        language_helper.set_comparing_all_locales(compare_all);
+
+        if language_helper.is_watching_clipboard() {
+            ui.label(RichText::new(tr!("👁 watching clipboard")).small().weak())
+                .on_hover_text(tr!("copied text is searched automatically (see settings)"));
+        }
+    });
+}
+
+// whole chat lines, every known name is replaced and highlighted
+fn create_phrase_translator(language_helper: &mut LanguageHelperSubsystem, ui: &mut egui::Ui) {
+    CollapsingHeader::new(RichText::new(tr!("Phrase Translator")).small())
+        .id_salt("language_helper_phrase_translator")
+        .show(ui, |ui| {
+            let mut phrase = language_helper.get_phrase();
+            ui.add(
+                egui::TextEdit::multiline(&mut phrase)
+                    .hint_text(tr!(
+                        "paste a chat line, e.g. \"WTS Leftovers and Timid Garchomp\""
+                    ))
+                    .desired_rows(2)
+                    .desired_width(ui.available_width()),
+            );
+            language_helper.set_phrase(phrase);
+
+            let segments = language_helper.get_phrase_translation();
+            if segments.is_empty() {
+                return;
+            }
+
+            ui.horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing.x = 0.;
+                for segment in &segments {
+                    match segment {
+                        PhraseSegment::Kept(text) => {
+                            ui.label(text);
+                        }
+                        PhraseSegment::Translated {
+                            original,
+                            translation,
+                            category,
+                        } => {
+                            ui.label(
+                                RichText::new(translation)
+                                    .strong()
+                                    .color(Color32::LIGHT_GREEN),
+                            )
+                            .on_hover_text(format!(
+                                "{original} ({})",
+                                tr!(category.display_name())
+                            ));
+                        }
+                    }
+                }
+            });
+
+            let translated_phrase: String = segments
+                .iter()
+                .map(|segment| match segment {
+                    PhraseSegment::Kept(text) => text.as_str(),
+                    PhraseSegment::Translated { translation, .. } => translation.as_str(),
+                })
+                .collect();
+            if ui
+                .button(RichText::new(tr!("📋 Copy translated line")).small())
+                .clicked()
+            {
+                language_helper.note_own_copy(&translated_phrase);
+                ui.ctx().copy_text(translated_phrase);
             }
         });
 }
@@ -219,7 +545,7 @@ fn create_searchbar(language_helper: &mut LanguageHelperSubsystem, ui: &mut egui
             ui.add(
                 egui::TextEdit::singleline(&mut search_prompt)
                     .frame(false) // disable default frame!
-                    .hint_text("search pokemon / items / moves / locations")
+                    .hint_text(tr!("search pokemon / items / moves / locations"))
                     .clip_text(false) //does nothing
                     .desired_width(ui.available_width())
                     .margin(Margin::symmetric(6, 2)),
@@ -229,6 +555,8 @@ fn create_searchbar(language_helper: &mut LanguageHelperSubsystem, ui: &mut egui
 
     language_helper.set_search_prompt(search_prompt);
 
+    create_category_filters(language_helper, ui);
+
     // has focus update for next frame
     let search_focussed = response.has_focus();
     ui.memory_mut(|w| w.data.insert_temp(search_focus_id, search_focussed));
@@ -1,5 +1,5 @@
 use crate::frontend::style;
-use crate::{app::OverlayApp, backend::feature_state::Feature};
+use crate::{app::OverlayApp, backend::feature_state::Feature, tr};
 use egui::{Align2, Color32, Image, ImageButton, Vec2, Window};
 use std::collections::HashMap;
 use strum::IntoEnumIterator;
@@ -28,6 +28,45 @@ pub fn draw_gui(ctx: &egui::Context, _frame: &mut eframe::Frame, state: &mut Ove
     draw_language_helper_panel(ctx, state);
 
     draw_options_panel(ctx, state);
+
+    draw_network_prompt(ctx, state);
+}
+
+////////////////////////////////////////////////////////////////////////////
+///  Network Prompt ("Ask first" network mode)
+////////////////////////////////////////////////////////////////////////////
+fn draw_network_prompt(ctx: &egui::Context, state: &mut OverlayApp) {
+    let waiting = state.network.get_waiting_requests();
+    if waiting.is_empty() {
+        return;
+    }
+
+    Window::new(tr!("Network Access"))
+        .id(egui::Id::new("Network Access"))
+        .frame(style::CUSTOM_FRAME_FOCUSSED)
+        .anchor(Align2::CENTER_TOP, Vec2::new(0., 20.))
+        .collapsible(false)
+        .resizable(false)
+        .auto_sized()
+        .show(ctx, |ui| {
+            ui.label(tr!("The Companion App wants to download:"));
+            for url in &waiting {
+                ui.label(format!("    {url}"));
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .button(tr!("Allow"))
+                    .on_hover_text(tr!("allows downloads until the app is closed"))
+                    .clicked()
+                {
+                    state.network.answer_waiting_requests(true);
+                }
+                if ui.button(tr!("Deny")).clicked() {
+                    state.network.answer_waiting_requests(false);
+                }
+            });
+            ui.label(tr!("(Settings -> Network to change when the app asks)"));
+        });
 }
 
 ////////////////////////////////////////////////////////////////////////////
@@ -80,7 +119,7 @@ pub fn draw_control_panel(ctx: &egui::Context, state: &mut OverlayApp) {
                         feature_button = feature_button.selected(true);
                     }
 
-                    let mut hover_text = feature.get_name();
+                    let mut hover_text = tr!(&feature.get_name());
                     hover_text.push_str(
                         format!(" {}", feature_shortcuts.get(&feature).unwrap_or(&" - ")).as_str(),
                     );
@@ -92,8 +131,8 @@ pub fn draw_control_panel(ctx: &egui::Context, state: &mut OverlayApp) {
                     }
                 }
 
-                let shutdown_image =
-                    Image::new(state.gui.get_image_source("shutdown_button")).alt_text("Close");
+                let shutdown_image = Image::new(state.gui.get_image_source("shutdown_button"))
+                    .alt_text(tr!("Close"));
                 ui.style_mut().visuals.widgets.inactive.weak_bg_fill =
                     Color32::from_rgb(110, 32, 32);
                 ui.style_mut().visuals.widgets.hovered.weak_bg_fill =
@@ -103,7 +142,7 @@ pub fn draw_control_panel(ctx: &egui::Context, state: &mut OverlayApp) {
 
                 if ui
                     .add(close_btn)
-                    .on_hover_text("Close Companion App (Ctrl+D)")
+                    .on_hover_text(tr!("Close Companion App (Ctrl+D)"))
                     .clicked()
                     || ctx.input(|i| i.key_pressed(egui::Key::D) && i.modifiers.ctrl)
                 {
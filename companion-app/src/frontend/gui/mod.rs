@@ -1,6 +1,7 @@
 pub mod language_helper;
 pub mod main_gui;
 pub mod notes;
+pub mod resource_editor;
 pub mod resources;
 pub mod settings;
 pub mod type_matrix;
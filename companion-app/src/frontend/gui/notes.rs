@@ -4,6 +4,7 @@ use crate::{
     app::OverlayApp,
     backend::{self, feature_state::Feature},
     frontend::utils::construct_base_window,
+    tr,
 };
 
 ////////////////////////////////////////////////////////////////////////////
@@ -42,7 +43,7 @@ pub fn draw_notes_panel(ctx: &egui::Context, state: &mut OverlayApp) {
                         TextEdit::multiline(&mut state.notes.text)
                             .frame(false)
                             .interactive(!is_alt_down)
-                            .hint_text("...\nType personal notes and TODOs in here to keep track of them.\n...")
+                            .hint_text(tr!("...\nType personal notes and TODOs in here to keep track of them.\n..."))
                             .clip_text(false)//does nothing
                             .desired_width(ui.available_width())
                             .font(egui::TextStyle::Body)
@@ -78,7 +79,7 @@ pub fn draw_notes_panel(ctx: &egui::Context, state: &mut OverlayApp) {
                     .stroke(Stroke{width:0.5, color: Color32::from_white_alpha(150)})
                     .show(ui, |ui| {
                         ui.add_sized(Vec2::splat(20.),Label::new("?"));                        
-                    }).response.on_hover_text("Simple notes styling:\n# Heading 1\n## Heading 2 ...\n_underlined_\n*italic*");
+                    }).response.on_hover_text(tr!("Simple notes styling:\n# Heading 1\n## Heading 2 ...\n_underlined_\n*italic*"));
             });
 
         });
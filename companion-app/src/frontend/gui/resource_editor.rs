@@ -0,0 +1,340 @@
+use egui::{Button, Color32, ComboBox, Key, Modifiers, RichText, TextEdit};
+use egui_commonmark::CommonMarkViewer;
+
+use crate::{
+    backend::{
+        resource_cards::{
+            ItemCardResource, LocationCardResource, MoveCardResource, PokemonCardResource,
+        },
+        resource_editor::ResourceEditor,
+        resource_packs::PACK_KEY_PREFIX,
+        resource_parser::PageElement,
+        ressources_feature::{
+            INDEX_RESOURCE, PATH_SEPARATOR, Resource, RessourcesSubsystem, heading_markdown,
+            resource_display_name, split_anchor,
+        },
+    },
+    frontend::style,
+    tr,
+};
+
+////////////////////////////////////////////////////////////////////////////
+///  Resource Editor (split view inside the resources window)
+////////////////////////////////////////////////////////////////////////////
+pub fn draw_resource_editor(
+    editor: &mut ResourceEditor,
+    resources_sub: &mut RessourcesSubsystem,
+    ui: &mut egui::Ui,
+) {
+    // the resources window has to pick up whatever was written to disk
+    let mut reload_showing: Option<Option<String>> = None;
+
+    let save_shortcut = ui.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::S));
+    if save_shortcut && editor.has_unsaved_changes() {
+        reload_showing = save_page(editor);
+    }
+
+    if let Some(reload) = draw_editor_toolbar(editor, ui) {
+        reload_showing = Some(reload);
+    }
+    ui.separator();
+
+    if editor.get_editing_page().is_none() {
+        ui.label(tr!("Pick a page to edit or create a new one."));
+    } else {
+        ui.columns(2, |columns| {
+            egui::ScrollArea::vertical()
+                .id_salt("resource_editor_text")
+                .auto_shrink([false, false])
+                .show(&mut columns[0], |ui| {
+                    let output = TextEdit::multiline(&mut editor.text)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(20)
+                        .show(ui);
+                    if let Some(cursor_range) = output.cursor_range {
+                        editor.cursor_char_index = Some(cursor_range.primary.ccursor.index);
+                    }
+                });
+
+            egui::ScrollArea::vertical()
+                .id_salt("resource_editor_preview")
+                .auto_shrink([false, false])
+                .show(&mut columns[1], |ui| {
+                    draw_preview(editor, resources_sub, ui);
+                });
+        });
+    }
+
+    if let Some(show_page) = reload_showing {
+        resources_sub.reload_resources();
+        if let Some(key) = show_page {
+            resources_sub.set_current_resource(key, true);
+        }
+    }
+}
+
+fn save_page(editor: &mut ResourceEditor) -> Option<Option<String>> {
+    match editor.save() {
+        Ok(()) => Some(editor.get_editing_page().cloned()),
+        Err(e) => {
+            editor.status = Some(tr!("couldn't save: {}", e));
+            None
+        }
+    }
+}
+
+// page picker, file actions and the tag helpers
+// returns Some(page to show) when files on disk changed
+fn draw_editor_toolbar(editor: &mut ResourceEditor, ui: &mut egui::Ui) -> Option<Option<String>> {
+    let mut reload_showing = None;
+    let unsaved = editor.has_unsaved_changes();
+
+    ui.horizontal_wrapped(|ui| {
+        // PAGE PICKER
+        let selected_text = match editor.get_editing_page() {
+            Some(key) if unsaved => format!("{key} ●"),
+            Some(key) => key.clone(),
+            None => tr!("pick a page"),
+        };
+        let mut picked_page = None;
+        ui.add_enabled_ui(!unsaved, |ui| {
+            ComboBox::from_id_salt("resource_editor_page")
+                .selected_text(selected_text)
+                .width(180.)
+                .show_ui(ui, |ui| {
+                    for key in editor.get_pages() {
+                        let is_selected = editor.get_editing_page() == Some(key);
+                        if ui.selectable_label(is_selected, key).clicked() {
+                            picked_page = Some(key.clone());
+                        }
+                    }
+                })
+                .response
+                .on_disabled_hover_text(tr!("save or discard your changes first"));
+        });
+        if let Some(key) = picked_page {
+            editor.open_page(&key);
+        }
+
+        // SAVE & DISCARD
+        if ui
+            .add_enabled(unsaved, Button::new(tr!("💾 Save")))
+            .on_hover_text("Ctrl + S")
+            .clicked()
+        {
+            reload_showing = save_page(editor);
+        }
+        if unsaved
+            && ui.button(tr!("↺ Discard")).clicked()
+            && let Some(key) = editor.get_editing_page().cloned()
+        {
+            editor.open_page(&key);
+        }
+
+        // NEW PAGE
+        ui.menu_button(tr!("➕ New"), |ui| {
+            ui.label(tr!("Name (use \"/\" for folders)"));
+            ui.add(TextEdit::singleline(&mut editor.new_page_name).hint_text("Guides/My-Guide"));
+            if ui
+                .add_enabled(!unsaved, Button::new(tr!("Create")))
+                .clicked()
+            {
+                let name = std::mem::take(&mut editor.new_page_name);
+                match editor.create_page(&name) {
+                    Ok(key) => reload_showing = Some(Some(key)),
+                    Err(e) => {
+                        editor.new_page_name = name;
+                        editor.status = Some(tr!("couldn't create page: {}", e));
+                    }
+                }
+                ui.close_menu();
+            }
+        });
+
+        let has_page = editor.get_editing_page().is_some();
+        ui.add_enabled_ui(has_page, |ui| {
+            // RENAME
+            ui.menu_button(tr!("✏ Rename"), |ui| {
+                if editor.rename_target.is_empty()
+                    && let Some(key) = editor.get_editing_page()
+                {
+                    editor.rename_target = key.clone();
+                }
+                ui.label(tr!("New name"));
+                ui.text_edit_singleline(&mut editor.rename_target);
+                if ui.button(tr!("Rename")).clicked() {
+                    let name = std::mem::take(&mut editor.rename_target);
+                    match editor.rename_page(&name) {
+                        Ok(key) => reload_showing = Some(Some(key)),
+                        Err(e) => editor.status = Some(tr!("couldn't rename page: {}", e)),
+                    }
+                    ui.close_menu();
+                }
+            });
+
+            // DELETE (the menu doubles as confirmation)
+            ui.menu_button(tr!("🗑 Delete"), |ui| {
+                if let Some(key) = editor.get_editing_page() {
+                    ui.label(tr!("Delete \"{}\" for good?", key));
+                }
+                if ui
+                    .button(RichText::new(tr!("Delete")).color(Color32::LIGHT_RED))
+                    .clicked()
+                {
+                    match editor.delete_page() {
+                        Ok(()) => reload_showing = Some(None),
+                        Err(e) => editor.status = Some(tr!("couldn't delete page: {}", e)),
+                    }
+                    ui.close_menu();
+                }
+            });
+        });
+
+        ui.separator();
+
+        ui.add_enabled_ui(has_page, |ui| {
+            // APPLINK PICKER, absolute links so they keep working when the page is moved
+            ui.menu_button("🔗 AppLink", |ui| {
+                let mut targets: Vec<&String> = editor.get_page_keys().iter().collect();
+                targets.sort_by_key(|key| key.to_lowercase());
+
+                let mut tag = None;
+                egui::ScrollArea::vertical()
+                    .max_height(300.)
+                    .show(ui, |ui| {
+                        for key in targets {
+                            if ui.button(key).clicked() {
+                                tag = Some(format!(
+                                    "<AppLink={}, /{key}>",
+                                    resource_display_name(key)
+                                ));
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                if let Some(tag) = tag {
+                    editor.insert_tag(&tag);
+                }
+            });
+
+            // WEBLINK HELPER
+            ui.menu_button("🌐 WebLink", |ui| {
+                ui.add(TextEdit::singleline(&mut editor.web_link_text).hint_text(tr!("Text")));
+                ui.add(TextEdit::singleline(&mut editor.web_link_url).hint_text("https://..."));
+                let can_insert = !editor.web_link_text.trim().is_empty()
+                    && !editor.web_link_url.trim().is_empty();
+                if ui
+                    .add_enabled(can_insert, Button::new(tr!("Insert")))
+                    .clicked()
+                {
+                    let tag = format!(
+                        "<WebLink={}, {}>",
+                        editor.web_link_text.trim(),
+                        editor.web_link_url.trim()
+                    );
+                    editor.insert_tag(&tag);
+                    editor.web_link_text.clear();
+                    editor.web_link_url.clear();
+                    ui.close_menu();
+                }
+            });
+        });
+
+        ui.separator();
+
+        if ui
+            .add_enabled(!unsaved, Button::new(tr!("✖ Close Editor")))
+            .on_disabled_hover_text(tr!("save or discard your changes first"))
+            .clicked()
+        {
+            editor.active = false;
+        }
+
+        if let Some(status) = &editor.status {
+            ui.label(RichText::new(status).weak());
+        }
+    });
+
+    reload_showing
+}
+
+// renders the parsed text close to how the resources window will show it
+fn draw_preview(
+    editor: &mut ResourceEditor,
+    resources_sub: &RessourcesSubsystem,
+    ui: &mut egui::Ui,
+) {
+    let page_keys = editor.get_page_keys().clone();
+    let Some((page, markdown_cache)) = editor.get_preview() else {
+        return;
+    };
+
+    for issue in &page.issues {
+        ui.label(
+            RichText::new(tr!("⚠ line {}: {}", issue.line, issue.message)).color(Color32::YELLOW),
+        );
+    }
+
+    for (line, element) in &page.elements {
+        let preview_key = format!("inner_editor_preview_{line}");
+        match element {
+            PageElement::Markdown(markdown) => {
+                CommonMarkViewer::new().show(ui, markdown_cache, markdown);
+            }
+            PageElement::Heading(heading) => {
+                CommonMarkViewer::new().show(
+                    ui,
+                    markdown_cache,
+                    &heading_markdown(heading.level, &heading.text),
+                );
+            }
+            PageElement::AppLink { text, target, .. } => {
+                let (target_page, _) = split_anchor(target);
+                let text = text
+                    .clone()
+                    .unwrap_or_else(|| resources_sub.get_page_title(target_page));
+                let exists = target_page.is_empty()
+                    || target_page == PACK_KEY_PREFIX
+                    || target_page.split(PATH_SEPARATOR).next() == Some(INDEX_RESOURCE)
+                    || page_keys.contains(target_page);
+                let label = match exists {
+                    true => RichText::new(format!("→ {text}")).color(style::COLOR_APPLINK_REST),
+                    false => RichText::new(tr!("⚠ {} (no page \"{}\")", text, target_page))
+                        .color(Color32::YELLOW),
+                };
+                ui.label(label).on_hover_text(target);
+            }
+            PageElement::WebLink { text, url } => {
+                ui.label(RichText::new(format!("🌐 {text}")).color(style::COLOR_HYPERLINK))
+                    .on_hover_text(url);
+            }
+            PageElement::DataCard {
+                tag_type,
+                name,
+                extra,
+            } => {
+                let card: Box<dyn Resource> = match tag_type.as_str() {
+                    "Pokemon" => Box::new(PokemonCardResource::new(preview_key, name)),
+                    "Item" => Box::new(ItemCardResource::new(preview_key, name)),
+                    "Move" => Box::new(MoveCardResource::new(preview_key, name)),
+                    _ => Box::new(LocationCardResource::new(preview_key, name, extra.clone())),
+                };
+                card.render_resource(resources_sub, ui);
+            }
+            PageElement::Checkbox { text, checked, .. } => {
+                ui.add_enabled(false, egui::Checkbox::new(&mut checked.clone(), text));
+            }
+            PageElement::Include { target, .. } => {
+                // the included page isn't necessarily saved yet
+                ui.label(RichText::new(tr!("⤷ Include: {}", target)).color(style::COLOR_HEADING_2))
+                    .on_hover_text(tr!("shown in the resources window after saving"));
+            }
+            PageElement::Table { file } => {
+                // tables are read from disk, so they only show up in the resources window
+                ui.label(RichText::new(tr!("▦ Table: {}", file)).color(style::COLOR_HEADING_2))
+                    .on_hover_text(tr!("shown in the resources window after saving"));
+            }
+        }
+    }
+}
@@ -1,13 +1,26 @@
 use crate::frontend::style;
 use crate::{
     app::OverlayApp,
-    backend::{feature_state::Feature, ressources_feature::RessourcesSubsystem},
+    backend::{
+        feature_state::Feature,
+        resource_editor::ResourceEditor,
+        ressources_feature::{ROOT_RESOURCE, RessourcesSubsystem},
+    },
     frontend::{
+        gui::resource_editor,
         gui_subsystem::GuiSubsystem,
         utils::{self, construct_base_window},
     },
+    tr,
+};
+use egui::{
+    Button, Color32, Frame, Image, ImageButton, Label, Layout, Margin, PointerButton, Rect,
+    RichText, Sense, SidePanel, Stroke, UiBuilder, Vec2, pos2,
 };
-use egui::{Color32, Frame, Image, ImageButton, Layout, Margin, Stroke, UiBuilder, Vec2};
+
+// the first bookmarks get a button in the nav bar, the rest are in the 🔖 dropdown
+const NAV_BAR_PINNED_BOOKMARKS: usize = 3;
+const NAV_BAR_BOOKMARK_MAX_CHARS: usize = 14;
 
 ////////////////////////////////////////////////////////////////////////////
 ///  Resources Window
@@ -21,20 +34,178 @@ pub fn draw_ressources_panel(ctx: &egui::Context, state: &mut OverlayApp) {
     construct_base_window("Ressources", state.viewport_manager.as_ref())
         .open(window_open)
         .show(ctx, |ui| {
+            // data cards follow the "UI Language" setting
+            state
+                .ressources
+                .set_display_locale(&state.settings.ui_locale);
+            // pick up freshly installed/removed resource packs
+            state
+                .ressources
+                .sync_with_resource_packs(state.resource_packs.get_install_counter());
+
+            // editor mode replaces the page with a split view of the raw md-file and a preview
+            if state.resource_editor.active {
+                resource_editor::draw_resource_editor(
+                    &mut state.resource_editor,
+                    &mut state.ressources,
+                    ui,
+                );
+                return;
+            }
+
+            draw_table_of_contents(&mut state.ressources, ui);
+            draw_breadcrumbs(&mut state.ressources, ui);
+            draw_checklist_progress(&mut state.ressources, ui);
+
+            let mut scroll_area = egui::ScrollArea::vertical().auto_shrink([false, false]);
+            if let Some(offset) = state.ressources.take_pending_scroll_offset() {
+                scroll_area = scroll_area.vertical_scroll_offset(offset);
+            }
+            let scroll_output = scroll_area.show(ui, |ui| {
+                ui.add_space(5.);
+                state.ressources.render_current_resource(ui);
+                ui.add_space(19.);
+            });
+            state
+                .ressources
+                .set_current_scroll_offset(scroll_output.state.offset.y);
+
+            draw_resource_nav_bar(
+                &mut state.ressources,
+                &mut state.resource_editor,
+                &state.gui,
+                ui,
+            );
+
+            // mouse buttons 4 & 5 navigate like in a browser
+            if state.viewport_manager.current_focus_state().is_focused() {
+                let (back_pressed, forward_pressed) = ui.input(|i| {
+                    (
+                        i.pointer.button_pressed(PointerButton::Extra1),
+                        i.pointer.button_pressed(PointerButton::Extra2),
+                    )
+                });
+                if back_pressed {
+                    state.ressources.go_back_visited_resources();
+                }
+                if forward_pressed {
+                    state.ressources.go_forward_visited_resources();
+                }
+            }
+        });
+}
+
+// only shown on pages with "- [ ]" items
+fn draw_checklist_progress(resources_sub: &mut RessourcesSubsystem, ui: &mut egui::Ui) {
+    let Some((checked, total)) = resources_sub.get_checklist_progress() else {
+        return;
+    };
+
+    let mut reset = false;
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::ProgressBar::new(checked as f32 / total as f32)
+                .desired_width(150.)
+                .text(tr!("{} / {} done", checked, total)),
+        );
+        // the menu doubles as confirmation
+        ui.add_enabled_ui(checked > 0, |ui| {
+            ui.menu_button(tr!("↺ Reset"), |ui| {
+                ui.label(tr!("Uncheck all items of this page?"));
+                if ui.button(tr!("Reset")).clicked() {
+                    reset = true;
+                    ui.close_menu();
+                }
+            });
+        });
+    });
+
+    if reset {
+        resources_sub.reset_current_checklist();
+    }
+}
+
+// collapsible side panel listing all headings of the current page
+fn draw_table_of_contents(resources_sub: &mut RessourcesSubsystem, ui: &mut egui::Ui) {
+    let table_of_contents = resources_sub.get_current_table_of_contents();
+    let show_panel = resources_sub.show_table_of_contents && !table_of_contents.is_empty();
+
+    let mut clicked_anchor = None;
+    SidePanel::left("resources_table_of_contents")
+        .resizable(true)
+        .default_width(130.)
+        .frame(Frame::new().inner_margin(Margin::symmetric(0, 4)))
+        .show_animated_inside(ui, show_panel, |ui| {
+            ui.label(RichText::new(tr!("Contents")).heading());
             egui::ScrollArea::vertical()
                 .auto_shrink([false, false])
                 .show(ui, |ui| {
-                    ui.add_space(5.);
-                    state.ressources.render_current_resource(ui);
-                    ui.add_space(19.);
+                    // indent relative to the highest heading level present on the page
+                    let min_level = table_of_contents.iter().map(|e| e.level).min();
+                    for entry in &table_of_contents {
+                        let indent = (entry.level - min_level.unwrap_or(entry.level)) as f32;
+                        ui.horizontal(|ui| {
+                            ui.add_space(indent * 10.);
+                            let response =
+                                ui.add(Label::new(&entry.text).truncate().sense(Sense::click()));
+                            if response.hovered() {
+                                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                                utils::draw_highlight_underline(ui, &response, 0.);
+                            }
+                            if response.clicked() {
+                                clicked_anchor = Some(entry.anchor.clone());
+                            }
+                        });
+                    }
                 });
-            draw_resource_nav_bar(&mut state.ressources, &state.gui, ui);
         });
+
+    if let Some(anchor) = clicked_anchor {
+        resources_sub.set_current_resource(format!("#{anchor}"), true);
+    }
+}
+
+// "ROOT > Guides > Ho-Oh-Guide" row, every parent category is clickable
+fn draw_breadcrumbs(resources_sub: &mut RessourcesSubsystem, ui: &mut egui::Ui) {
+    let breadcrumbs = resources_sub.get_breadcrumbs();
+    if breadcrumbs.len() <= 1 {
+        return; // nothing to show on the ROOT page
+    }
+
+    let mut clicked_key = None;
+    ui.horizontal_wrapped(|ui| {
+        ui.style_mut().spacing.item_spacing = Vec2::new(4., 2.);
+        let last_index = breadcrumbs.len() - 1;
+        for (i, (key, label)) in breadcrumbs.iter().enumerate() {
+            if i == last_index {
+                ui.label(RichText::new(label).strong());
+                break;
+            }
+            let response = ui.add(
+                Label::new(RichText::new(label).color(style::COLOR_APPLINK_REST))
+                    .sense(Sense::click()),
+            );
+            if response.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                utils::draw_highlight_underline(ui, &response, 0.);
+            }
+            if response.clicked() {
+                clicked_key = Some(key.clone());
+            }
+            ui.label("›");
+        }
+    });
+    ui.separator();
+
+    if let Some(key) = clicked_key {
+        resources_sub.set_current_resource(key, true);
+    }
 }
 
 // drawing an overlay over the the same space ui already occupies and creates a navbar on it
 fn draw_resource_nav_bar(
     resources_sub: &mut RessourcesSubsystem,
+    resource_editor: &mut ResourceEditor,
     gui_sub: &GuiSubsystem,
     ui: &mut egui::Ui,
 ) {
@@ -75,10 +246,12 @@ fn draw_resource_nav_bar(
 
                     // HOME BUTTON
                     let home_image = Image::new(home_icon_source)
-                        .alt_text("Home")
+                        .alt_text(tr!("Home"))
                         .tint(style::COLOR_APPLINK_REST);
                     let home_btn = ImageButton::new(home_image).corner_radius(button_size.x / 2.);
-                    let home_response = ui.add_sized(button_size, home_btn).on_hover_text("Home");
+                    let home_response = ui
+                        .add_sized(button_size, home_btn)
+                        .on_hover_text(tr!("Home"));
 
                     if home_response.hovered() {
                         // draw highlight
@@ -86,12 +259,36 @@ fn draw_resource_nav_bar(
                     }
 
                     if home_response.clicked() {
-                        resources_sub.set_current_resource("ROOT", true);
+                        resources_sub.set_current_resource(ROOT_RESOURCE, true);
+                    }
+
+                    // FORWARD BUTTON (mirrored back icon, right-to-left layout puts it after back)
+                    let forward_image = Image::new(back_icon_source.clone())
+                        .alt_text(tr!("forward"))
+                        .uv(Rect::from_min_max(pos2(1., 0.), pos2(0., 1.)))
+                        .tint(style::COLOR_APPLINK_REST);
+                    let forward_btn =
+                        ImageButton::new(forward_image).corner_radius(button_size.x / 2.);
+                    let forward_response = ui.add_enabled(
+                        resources_sub.inspect_next_resource().is_some(),
+                        |ui: &mut egui::Ui| ui.add_sized(button_size, forward_btn),
+                    );
+
+                    if let Some(next_res) = resources_sub.inspect_next_resource() {
+                        forward_response.clone().on_hover_text(next_res);
+                    }
+
+                    if forward_response.hovered() {
+                        utils::draw_highlight_underline(ui, &forward_response, 0.);
+                    }
+
+                    if forward_response.clicked() {
+                        resources_sub.go_forward_visited_resources();
                     }
 
                     // BACK BUTTON
                     let back_image = Image::new(back_icon_source)
-                        .alt_text("back")
+                        .alt_text(tr!("back"))
                         .tint(style::COLOR_APPLINK_REST);
                     let back_btn = ImageButton::new(back_image).corner_radius(button_size.x / 2.);
                     let back_response = ui.add_sized(button_size, back_btn);
@@ -110,6 +307,154 @@ fn draw_resource_nav_bar(
                         // back action
                         resources_sub.go_back_visited_resources();
                     }
+
+                    // BOOKMARK TOGGLE for the current page
+                    if let Some(current_key) = resources_sub.get_current_resource_key().cloned() {
+                        let is_bookmarked = resources_sub.is_bookmarked(&current_key);
+                        let bookmark_btn = Button::new(
+                            RichText::new(if is_bookmarked { "★" } else { "☆" })
+                                .size(16.)
+                                .color(style::COLOR_APPLINK_REST),
+                        )
+                        .corner_radius(button_size.x / 2.);
+                        let bookmark_response = ui
+                            .add_sized(button_size, bookmark_btn)
+                            .on_hover_text(match is_bookmarked {
+                                true => tr!("Remove Bookmark"),
+                                false => tr!("Bookmark this page"),
+                            });
+
+                        if bookmark_response.hovered() {
+                            utils::draw_highlight_underline(ui, &bookmark_response, 0.);
+                        }
+
+                        if bookmark_response.clicked() {
+                            resources_sub.toggle_bookmark(&current_key);
+                        }
+                    }
+
+                    // BOOKMARKS (overflow) & RECENTLY VISITED
+                    let mut clicked_key = None;
+                    let bookmarks = resources_sub.get_bookmarks();
+                    let pinned_count = bookmarks.len().min(NAV_BAR_PINNED_BOOKMARKS);
+                    ui.menu_button(
+                        RichText::new("🔖")
+                            .size(16.)
+                            .color(style::COLOR_APPLINK_REST),
+                        |ui| {
+                            if bookmarks.is_empty() {
+                                ui.label(RichText::new(tr!("Bookmarks")).strong());
+                                ui.label(tr!("none yet (☆)"));
+                                ui.separator();
+                            } else if bookmarks.len() > pinned_count {
+                                ui.label(RichText::new(tr!("More Bookmarks")).strong());
+                                for key in &bookmarks[pinned_count..] {
+                                    if ui.button(resources_sub.get_page_title(key)).clicked() {
+                                        clicked_key = Some(key.clone());
+                                        ui.close_menu();
+                                    }
+                                }
+                                ui.separator();
+                            }
+                            ui.label(RichText::new(tr!("Recently visited")).strong());
+                            for key in resources_sub.get_recent_resources() {
+                                if ui.button(resources_sub.get_page_title(key)).clicked() {
+                                    clicked_key = Some(key.clone());
+                                    ui.close_menu();
+                                }
+                            }
+                        },
+                    )
+                    .response
+                    .on_hover_text(tr!("Bookmarks & History"));
+
+                    // PINNED BOOKMARKS, reversed because the layout is right-to-left
+                    for key in bookmarks[..pinned_count].iter().rev() {
+                        let title = resources_sub.get_page_title(key);
+                        let short_title = match title.chars().count() > NAV_BAR_BOOKMARK_MAX_CHARS {
+                            true => format!(
+                                "{}…",
+                                title
+                                    .chars()
+                                    .take(NAV_BAR_BOOKMARK_MAX_CHARS - 1)
+                                    .collect::<String>()
+                            ),
+                            false => title.clone(),
+                        };
+                        let pinned_btn = Button::new(
+                            RichText::new(format!("★ {short_title}"))
+                                .color(style::COLOR_APPLINK_REST),
+                        )
+                        .selected(resources_sub.get_current_resource_key() == Some(key))
+                        .corner_radius(button_size.x / 2.);
+                        let pinned_response = ui
+                            .add_sized(Vec2::new(0., button_size.y), pinned_btn)
+                            .on_hover_text(title);
+
+                        if pinned_response.hovered() {
+                            utils::draw_highlight_underline(ui, &pinned_response, 0.);
+                        }
+
+                        if pinned_response.clicked() {
+                            clicked_key = Some(key.clone());
+                        }
+                    }
+
+                    if let Some(key) = clicked_key {
+                        resources_sub.set_current_resource(key, true);
+                    }
+
+                    // TABLE OF CONTENTS TOGGLE (only useful on pages with headings)
+                    if !resources_sub.get_current_table_of_contents().is_empty() {
+                        let toc_btn = Button::new(
+                            RichText::new("☰")
+                                .size(16.)
+                                .color(style::COLOR_APPLINK_REST),
+                        )
+                        .selected(resources_sub.show_table_of_contents)
+                        .corner_radius(button_size.x / 2.);
+                        let toc_response = ui
+                            .add_sized(button_size, toc_btn)
+                            .on_hover_text(tr!("Table of Contents"));
+
+                        if toc_response.hovered() {
+                            utils::draw_highlight_underline(ui, &toc_response, 0.);
+                        }
+
+                        if toc_response.clicked() {
+                            resources_sub.show_table_of_contents =
+                                !resources_sub.show_table_of_contents;
+                        }
+                    }
+
+                    // EDIT the md-file of the current page
+                    let edit_btn = Button::new(
+                        RichText::new("✏")
+                            .size(16.)
+                            .color(style::COLOR_APPLINK_REST),
+                    )
+                    .corner_radius(button_size.x / 2.);
+                    let edit_response = ui
+                        .add_sized(button_size, edit_btn)
+                        .on_hover_text(tr!("Edit Resources"));
+
+                    if edit_response.hovered() {
+                        utils::draw_highlight_underline(ui, &edit_response, 0.);
+                    }
+
+                    if edit_response.clicked() {
+                        resource_editor.activate(resources_sub.get_current_resource_key());
+                    }
+
+                    // LOAD ISSUES (e.g. duplicate resource keys), so broken files don't go unnoticed
+                    let load_issues = resources_sub.get_load_issues();
+                    if !load_issues.is_empty() {
+                        ui.add_sized(
+                            button_size,
+                            Label::new(RichText::new("⚠").size(16.).color(Color32::YELLOW)),
+                        )
+                        .on_hover_text(load_issues.join("\n"));
+                    }
                 });
             });
     });
@@ -2,12 +2,21 @@ use std::rc::Rc;
 
 use crate::{
     app::OverlayApp,
-    backend::{feature_state::Feature, locales::LocaleSubsystem, settings::SettingsSubsystem},
+    backend::{
+        feature_state::Feature,
+        locale_coverage::BASE_LOCALE,
+        locales::LocaleSubsystem,
+        network::{NetworkAccess, NetworkMode},
+        resource_packs::ResourcePackSubsystem,
+        ressources_feature::RessourcesSubsystem,
+        settings::SettingsSubsystem,
+    },
     frontend::utils::construct_base_window,
+    tr,
 };
 use egui::{
-    Align, Button, Checkbox, Frame, Layout, Margin, Response, ScrollArea, Separator, Slider, Vec2,
-    Widget,
+    Align, Button, Checkbox, CollapsingHeader, ComboBox, Frame, Layout, Margin, Response, RichText,
+    ScrollArea, Separator, Slider, TextEdit, Vec2, Widget,
 };
 
 pub fn draw_options_panel(ctx: &egui::Context, state: &mut OverlayApp) {
@@ -28,10 +37,30 @@ pub fn draw_options_panel(ctx: &egui::Context, state: &mut OverlayApp) {
 
                                 transparent_bg_always_checkbox(ui, &mut state.settings);
 
+                                clipboard_translation_checkbox(ui, &mut state.settings);
+
                                 typematrix_scale_slider(ui, &mut state.settings);
 
+                                ui_locale_combobox(ui, &mut state.settings, &state.locales);
+
+                                network_section(ui, &mut state.settings, &state.network);
+
                                 locale_state_section(ui, state.locales.clone());
 
+                                locale_coverage_section(ui, &state.locales);
+
+                                resource_translations_section(
+                                    ui,
+                                    &state.ressources,
+                                    &state.locales,
+                                );
+
+                                resource_packs_section(
+                                    ui,
+                                    &state.resource_packs,
+                                    &mut state.settings,
+                                );
+
                                 reset_ui_data(ui, &mut state.settings);
                             });
                             state.settings.draw_dev_options(ui);
@@ -48,7 +77,7 @@ fn add_default_sized_setting(ui: &mut egui::Ui, widget: impl Widget) -> Response
 }
 
 fn disable_overlay_checkbox(ui: &mut egui::Ui, settings: &mut SettingsSubsystem) {
-    let checkbox = Checkbox::new(&mut settings.disable_overlay, "Disable Overlay");
+    let checkbox = Checkbox::new(&mut settings.disable_overlay, tr!("Disable Overlay"));
     if add_default_sized_setting(ui, checkbox).changed() {
         settings.request_viewport_restart = true;
     }
@@ -57,13 +86,23 @@ fn disable_overlay_checkbox(ui: &mut egui::Ui, settings: &mut SettingsSubsystem)
 fn transparent_bg_always_checkbox(ui: &mut egui::Ui, settings: &mut SettingsSubsystem) {
     let checkbox = Checkbox::new(
         &mut settings.transparent_background_always,
-        "Transparent BG\nalways",
+        tr!("Transparent BG\nalways"),
     );
     add_default_sized_setting(ui, checkbox);
 }
 
+fn clipboard_translation_checkbox(ui: &mut egui::Ui, settings: &mut SettingsSubsystem) {
+    let checkbox = Checkbox::new(
+        &mut settings.clipboard_translation,
+        tr!("Translate copied text\n(Language Helper)"),
+    );
+    add_default_sized_setting(ui, checkbox).on_hover_text(tr!(
+        "copied names are searched in the Language Helper automatically"
+    ));
+}
+
 fn reset_ui_data(ui: &mut egui::Ui, settings: &mut SettingsSubsystem) {
-    let trigger_button = Button::new("Reset UI Data (e.g. Window positions)");
+    let trigger_button = Button::new(tr!("Reset UI Data (e.g. Window positions)"));
     if add_default_sized_setting(ui, trigger_button).clicked() {
         settings.request_clear_ui_data = true;
     }
@@ -71,16 +110,40 @@ fn reset_ui_data(ui: &mut egui::Ui, settings: &mut SettingsSubsystem) {
 
 fn typematrix_scale_slider(ui: &mut egui::Ui, settings: &mut SettingsSubsystem) {
     let scale_slider = Slider::new(&mut settings.type_matrix_scale, 0.1..=3.0)
-        .text("TypeMatrix Scale")
+        .text(tr!("TypeMatrix Scale"))
         .prefix("x")
         .fixed_decimals(2);
     add_default_sized_setting(ui, scale_slider);
 }
 
+// language data-bound content (e.g. the data cards in resources) is displayed in
+fn ui_locale_combobox(
+    ui: &mut egui::Ui,
+    settings: &mut SettingsSubsystem,
+    locales: &Rc<LocaleSubsystem>,
+) {
+    let mut available_locales = locales.get_available_locales();
+    available_locales.sort();
+
+    ui.horizontal(|ui| {
+        ComboBox::from_id_salt("UI_Locale_Combobox")
+            .selected_text(locales.get_locale_display_name(&settings.ui_locale))
+            .show_ui(ui, |ui| {
+                for locale_key in available_locales {
+                    let display_name = locales.get_locale_display_name(&locale_key);
+                    ui.selectable_value(&mut settings.ui_locale, locale_key, display_name);
+                }
+            });
+        ui.label(tr!("UI Language"))
+            .on_hover_text(tr!("language of the app itself and of data-bound content"));
+    });
+    ui.add(Separator::default().grow(5.));
+}
+
 fn global_application_scale_slider(ctx: &egui::Context, ui: &mut egui::Ui) {
     let mut dpi = ctx.pixels_per_point();
     let scale_slider = Slider::new(&mut dpi, 0.25..=3.0)
-        .text("Global Application\nSize")
+        .text(tr!("Global Application\nSize"))
         .prefix("x")
         .fixed_decimals(2);
 
@@ -90,18 +153,61 @@ fn global_application_scale_slider(ctx: &egui::Context, ui: &mut egui::Ui) {
     }
 }
 
+// decides if and where the app downloads locales and resource packs from
+fn network_section(ui: &mut egui::Ui, settings: &mut SettingsSubsystem, network: &NetworkAccess) {
+    ui.with_layout(Layout::top_down(Align::Min), |ui| {
+        ui.heading(tr!("Network"));
+
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("Network_Mode_Combobox")
+                .selected_text(tr!(settings.network_mode.display_name()))
+                .show_ui(ui, |ui| {
+                    for mode in NetworkMode::ALL {
+                        ui.selectable_value(
+                            &mut settings.network_mode,
+                            mode,
+                            tr!(mode.display_name()),
+                        )
+                        .on_hover_text(tr!(mode.description()));
+                    }
+                });
+            ui.label(tr!("Network Access"));
+        });
+        ui.label(format!("    {}", tr!(settings.network_mode.description())));
+
+        ui.label(tr!("Mirror URL :")).on_hover_text(tr!(
+            "replaces github for every default download, leave empty to use github"
+        ));
+        ui.add(
+            TextEdit::singleline(&mut settings.mirror_url)
+                .hint_text("https://raw.githubusercontent.com/...")
+                .desired_width(f32::INFINITY),
+        );
+
+        let waiting = network.get_waiting_requests();
+        if !waiting.is_empty() {
+            ui.label(tr!(
+                "{} downloads are waiting for your permission",
+                waiting.len()
+            ));
+        }
+    });
+
+    ui.add(Separator::default().grow(5.));
+}
+
 fn locale_state_section(ui: &mut egui::Ui, locales: Rc<LocaleSubsystem>) {
     ui.with_layout(Layout::top_down(Align::Min), |ui| {
-        ui.heading("Locale Data");
+        ui.heading(tr!("Locale Data"));
 
         // Download button
         match &locales.is_initialized() {
             true => {
-                ui.label(format!(
+                ui.label(tr!(
                     "Locale Version : v{}",
                     locales.get_locale_definition_version()
                 ));
-                ui.label("Available Locales :");
+                ui.label(tr!("Available Locales :"));
                 let mut list: String = "    | ".into();
                 for locale_name in locales.get_available_locales() {
                     list += &locale_name;
@@ -110,14 +216,40 @@ fn locale_state_section(ui: &mut egui::Ui, locales: Rc<LocaleSubsystem>) {
                 ui.label(list);
             }
             false => {
-                ui.label("LocaleSubsystem not initialized yet");
+                ui.label(tr!("LocaleSubsystem not initialized yet"));
             }
         };
 
+        // user fixes from the Language Helper, updates don't touch them
+        ui.label(tr!("User Translations :"));
+        let override_counts = locales.get_user_override_counts();
+        if override_counts.is_empty() {
+            ui.label(format!(
+                "    {}",
+                tr!("none (right click a translation in the Language Helper to fix it)")
+            ));
+        }
+        for (locale_key, count) in &override_counts {
+            ui.label(format!(
+                "    {locale_key} : {}",
+                tr!("{} overridden", count)
+            ));
+        }
+        if !override_counts.is_empty()
+            && ui
+                .button(tr!("Copy User Translations (to contribute them)"))
+                .on_hover_text(tr!(
+                    "copies them as json, in the layout of the locale files"
+                ))
+                .clicked()
+        {
+            ui.ctx().copy_text(locales.export_user_overrides());
+        }
+
         if ui
             .add_sized(
                 Vec2::new(ui.available_width(), 30.),
-                Button::new("Re-Initialize"),
+                Button::new(tr!("Re-Initialize")),
             )
             .clicked()
         {
@@ -126,7 +258,7 @@ fn locale_state_section(ui: &mut egui::Ui, locales: Rc<LocaleSubsystem>) {
         if ui
             .add_sized(
                 Vec2::new(ui.available_width(), 30.),
-                Button::new("Update Locales"),
+                Button::new(tr!("Update Locales")),
             )
             .clicked()
         {
@@ -136,3 +268,173 @@ fn locale_state_section(ui: &mut egui::Ui, locales: Rc<LocaleSubsystem>) {
 
     ui.add(Separator::default().grow(5.));
 }
+
+// report for translators: what is missing, empty or still english in each locale
+fn locale_coverage_section(ui: &mut egui::Ui, locales: &Rc<LocaleSubsystem>) {
+    ui.with_layout(Layout::top_down(Align::Min), |ui| {
+        ui.heading(tr!("Locale Coverage"));
+        ui.label(tr!("Compared to {} (the base data)", BASE_LOCALE));
+
+        for locale in locales.get_coverage_report().iter() {
+            let title = format!(
+                "{} : {:.1}% ({})",
+                locale.locale_key,
+                locale.coverage() * 100.,
+                tr!("{} to check", locale.issue_count())
+            );
+            CollapsingHeader::new(title)
+                .id_salt(format!("locale_coverage_{}", locale.locale_key))
+                .show(ui, |ui| {
+                    for category in &locale.categories {
+                        let title = format!(
+                            "{} : {}/{}",
+                            tr!(category.category.display_name()),
+                            category.texts.translated_count(),
+                            category.texts.base_count
+                        );
+                        CollapsingHeader::new(title)
+                            .id_salt(format!(
+                                "locale_coverage_{}_{:?}",
+                                locale.locale_key, category.category
+                            ))
+                            .show(ui, |ui| {
+                                coverage_key_list(ui, &tr!("missing"), &category.texts.missing);
+                                coverage_key_list(ui, &tr!("empty"), &category.texts.empty);
+                                coverage_key_list(
+                                    ui,
+                                    &tr!("same as english"),
+                                    &category.texts.same_as_base,
+                                );
+                                coverage_key_list(
+                                    ui,
+                                    &tr!("not in base data"),
+                                    &category.texts.unknown,
+                                );
+                            });
+                    }
+                });
+        }
+    });
+
+    ui.add(Separator::default().grow(5.));
+}
+
+fn coverage_key_list(ui: &mut egui::Ui, label: &str, keys: &[String]) {
+    if keys.is_empty() {
+        return;
+    }
+    ui.label(format!("{label} ({}) :", keys.len()));
+    ui.label(RichText::new(format!("    {}", keys.join(", "))).weak());
+}
+
+fn resource_packs_section(
+    ui: &mut egui::Ui,
+    resource_packs: &ResourcePackSubsystem,
+    settings: &mut SettingsSubsystem,
+) {
+    ui.with_layout(Layout::top_down(Align::Min), |ui| {
+        ui.heading(tr!("Resource Packs"));
+
+        ui.label(tr!("Pack Server URL :"));
+        ui.add(TextEdit::singleline(&mut settings.resource_pack_url).desired_width(f32::INFINITY));
+        let has_pack_server = !settings.resource_pack_url.trim().is_empty();
+        if !has_pack_server {
+            ui.label(format!(
+                "    {}",
+                tr!("no pack server set yet, enter the URL of one to find packs (it serves an index.json)")
+            ));
+        }
+
+        ui.label(tr!("Installed :"));
+        let installed = resource_packs.get_installed_packs();
+        if installed.is_empty() {
+            ui.label(format!("    {}", tr!("none")));
+        }
+        for pack in installed {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "    {} v{} ({})",
+                    pack.name,
+                    pack.version,
+                    tr!("by {}", pack.author)
+                ));
+                if ui.small_button(tr!("Remove")).clicked() {
+                    resource_packs.uninstall(&pack.id);
+                }
+            });
+        }
+
+        // only known after "Check for Updates"
+        let installable = resource_packs.get_installable_packs();
+        if !installable.is_empty() {
+            ui.label(tr!("Available :"));
+        }
+        for pack in installable {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "    {} v{} ({})",
+                    pack.name,
+                    pack.version,
+                    tr!("by {}", pack.author)
+                ));
+                let action = match resource_packs.is_installed(&pack.id) {
+                    true => tr!("Update"),
+                    false => tr!("Install"),
+                };
+                if ui.small_button(action).clicked() {
+                    resource_packs.trigger_install(&settings.resource_pack_url, &pack.id);
+                }
+            });
+        }
+
+        if let Some(error) = resource_packs.get_last_error() {
+            ui.colored_label(egui::Color32::YELLOW, error);
+        }
+
+        if ui
+            .add_enabled_ui(has_pack_server, |ui| {
+                ui.add_sized(
+                    Vec2::new(ui.available_width(), 30.),
+                    Button::new(tr!("Check for Updates")),
+                )
+            })
+            .inner
+            .clicked()
+        {
+            resource_packs.trigger_update_check(&settings.resource_pack_url);
+        }
+    });
+
+    ui.add(Separator::default().grow(5.));
+}
+
+// report for translators: which resource pages still need a translation in each locale
+fn resource_translations_section(
+    ui: &mut egui::Ui,
+    resources: &RessourcesSubsystem,
+    locales: &Rc<LocaleSubsystem>,
+) {
+    let mut locale_keys = locales.get_available_locales();
+    locale_keys.sort();
+
+    ui.with_layout(Layout::top_down(Align::Min), |ui| {
+        ui.heading(tr!("Resource Translations"));
+        ui.label(tr!("Translations go into \"assets/resources/<LOCALE>/\""));
+
+        for (locale, missing_pages) in resources.get_missing_translations(&locale_keys) {
+            let title = match missing_pages.len() {
+                0 => format!("{locale} : {}", tr!("complete")),
+                n => format!("{locale} : {}", tr!("{} pages missing", n)),
+            };
+            CollapsingHeader::new(title)
+                .id_salt(format!("missing_translations_{locale}"))
+                .show(ui, |ui| {
+                    for page in missing_pages {
+                        ui.label(format!("    {page}"));
+                    }
+                });
+        }
+    });
+
+    ui.add(Separator::default().grow(5.));
+}
@@ -1,14 +1,17 @@
 use super::viewport::ViewportManager;
-use crate::frontend::style;
+use crate::{frontend::style, tr};
 use egui::{Ui, Vec2, Window};
 use egui_extras::{Size, StripBuilder};
 
+/// `window_name` is the english name, which also keeps the window's id (and position) the same in
+/// every UI language
 pub fn construct_base_window<'open>(
-    window_name: impl Into<egui::WidgetText>,
+    window_name: &str,
     // application_focused: bool,
     viewport_manager: &dyn ViewportManager,
 ) -> Window<'open> {
-    Window::new(window_name)
+    Window::new(tr!(window_name))
+        .id(egui::Id::new(window_name))
         // .frame(if application_focused {
         .frame(if viewport_manager.current_focus_state().is_focused() {
             style::CUSTOM_FRAME_FOCUSSED
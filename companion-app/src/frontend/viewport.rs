@@ -1,7 +1,7 @@
 #![allow(unused_imports)]
 
 use std::{
-    sync::{mpsc::{self, Receiver}, Arc},
+    sync::{atomic::{AtomicBool, Ordering}, mpsc::{self, Receiver}, Arc},
     thread,
 };
 
@@ -40,6 +40,8 @@ pub trait ViewportManager {
     fn current_focus_state(&self) -> FocusState;
     fn window_background_color(&self) -> egui::Rgba { egui::Rgba::TRANSPARENT       /*  style::COLOR_BG_NON_OVERLAY.into() */ }
     fn should_draw_gui(&self) -> bool {true}
+    /// whether Alt+Y (copy top translation) was pressed since the last call, also while the overlay isn't focused
+    fn take_copy_translation_request(&mut self) -> bool {false}
 
     // fn setup_focused_mode(&self);   // INFO: not easily possible because communication between
     // fn setup_closed_mode(&self);    // listener thread and main thread is limitied, because of
@@ -80,7 +82,7 @@ pub mod windows {
     use std::{ffi::OsString, os::windows::ffi::OsStringExt, sync::mpsc::Sender, time::{Duration, Instant}};
     use ::windows::{core::BOOL, Win32::{
         Foundation::{HWND, LPARAM}, Graphics::Gdi::{GetMonitorInfoW, MonitorFromWindow, HMONITOR, MONITORINFO, MONITOR_DEFAULTTONEAREST}, UI::{
-            Input::KeyboardAndMouse::{RegisterHotKey, MOD_ALT, VK_C, VK_F, VK_V}, WindowsAndMessaging::{
+            Input::KeyboardAndMouse::{RegisterHotKey, MOD_ALT, VK_C, VK_F, VK_V, VK_Y}, WindowsAndMessaging::{
                 DispatchMessageW, EnumWindows, GetDesktopWindow, GetMessageW, GetWindowLongW, GetWindowTextLengthW, GetWindowTextW, 
                 IsWindow, IsWindowVisible, SetForegroundWindow, SetWindowLongW, SetWindowPos, ShowWindow, TranslateMessage, GWL_EXSTYLE,
                 MSG, SWP_FRAMECHANGED, SWP_NOSIZE, SWP_NOZORDER, SW_HIDE, SW_RESTORE, SW_SHOW, SW_SHOWMAXIMIZED, WM_HOTKEY, WS_EX_LAYERED, WS_EX_TRANSPARENT
@@ -95,6 +97,7 @@ pub mod windows {
 
         focus_state_rx: Option<Receiver<FocusState>>,
         pokemmo_window_tx: Option<Sender<Option<isize>>>,
+        copy_translation_requested: Arc<AtomicBool>, // set by the listener thread
         
         overlay_hwnd_int: isize,
         pokemmo_hwnd_int: Option<isize>,
@@ -109,6 +112,7 @@ pub mod windows {
                 app_focus: FocusState::Focused,
                 focus_state_rx: None,
                 pokemmo_window_tx: None,
+                copy_translation_requested: Arc::new(AtomicBool::new(false)),
                 overlay_hwnd_int: 0,
                 pokemmo_hwnd_int:None,
                 winit_window,
@@ -139,6 +143,7 @@ pub mod windows {
         fn spawn_hotkey_listener_thread(&self) -> (Receiver<FocusState>, Sender<Option<isize>>) {
             let overlay_hwnd_int = self.overlay_hwnd_int;
             let pokemmo_hwnd_int= self.pokemmo_hwnd_int;
+            let copy_translation_requested = self.copy_translation_requested.clone();
 
             let (focus_state_tx, focus_state_rx): (Sender<FocusState>, Receiver<FocusState>) =
                 mpsc::channel();
@@ -157,6 +162,8 @@ pub mod windows {
                     .expect("failed to register hotkey for closing");
                 RegisterHotKey(None, 3, MOD_ALT, VK_V.0 as u32)
                     .expect("failed to register hotkey for closing");
+                RegisterHotKey(None, 4, MOD_ALT, VK_Y.0 as u32)
+                    .expect("failed to register hotkey for copying the top translation");
 
                 // thread has it's own Event loop only listening to all global HotKeys
                 let mut msg = MSG::default();
@@ -197,6 +204,11 @@ pub mod windows {
 
                                 let _ = focus_state_tx.send(FocusState::Unfocused); // notify main thread 
                             }
+                            4 => {
+                                println!("Copy top translation");
+
+                                copy_translation_requested.store(true, Ordering::Relaxed);
+                            }
                             _ => {}
                         }
                     }
@@ -249,6 +261,10 @@ pub mod windows {
         fn current_focus_state(&self) -> FocusState {
             self.app_focus
         }
+
+        fn take_copy_translation_request(&mut self) -> bool {
+            self.copy_translation_requested.swap(false, Ordering::Relaxed)
+        }
     }
 
     fn maximize_on_target_monitor(overlay_hwnd: HWND, target_monitor: HMONITOR)   {
@@ -399,6 +415,9 @@ pub mod unix {
     use x11rb::protocol::Event;
     use xkeysym::{self, key};
 
+    // shown by zenity before pkexec asks for sudo, translated via the UI language (ui.json)
+    const SUDO_EXPLANATION: &str = "On some linux desktop environments it is not possible to register global hotkeys. Wayland was detected as your DisplayCompositor, which prohibits global-hotkeys in general. One user-level approach would be to use the xdg-desktop-portal global hotkeys, but your desktop environment also didn't support it (e.g. GNOME DE supports it after version 48 (Ubuntu 25.04)). \nNow the only solution left for global hotkeys is a sudo key-reader (such programs can be security risk (key-loggers), so read my open source code and verify yourself). You will be prompted for sudo-privileges next. You can still deny the upcoming privileges request and use the app in a non-overlay way.";
+
     // use wayland_client::{
     //     Dispatch,
     //     QueueHandle,
@@ -419,6 +438,7 @@ pub mod unix {
     pub struct NativeViewportManagerX11 {
         app_focus: FocusState,
         focus_state_rx: Option<Receiver<FocusState>>,
+        copy_translation_requested: Arc<AtomicBool>, // set by the listener thread
     }
 
     impl NativeViewportManagerX11 {
@@ -426,6 +446,7 @@ pub mod unix {
             let mut manager = Self {
                 app_focus: FocusState::Focused,
                 focus_state_rx: None,
+                copy_translation_requested: Arc::new(AtomicBool::new(false)),
             };
 
             match window_handle.as_raw() {
@@ -455,10 +476,11 @@ pub mod unix {
             let root = screen.root;
             println!("Root window = 0x{:X}", root);
 
-            // Get KeyCodes for Alt+F/C/V
+            // Get KeyCodes for Alt+F/C/V/Y
             let f_kc = keysym_to_keycode(&conn, key::f)?;
             let c_kc = keysym_to_keycode(&conn, key::c)?;
             let v_kc = keysym_to_keycode(&conn, key::v)?;
+            let y_kc = keysym_to_keycode(&conn, key::y)?;
             let alt = ModMask::M1;
 
             // to notice a grabbed key I need to grab all variations with other mod keys that could
@@ -470,7 +492,7 @@ pub mod unix {
                 ModMask::LOCK | ModMask::M2, // both
             ];
             // Grab the keys
-            for kc in [f_kc, c_kc, v_kc] {
+            for kc in [f_kc, c_kc, v_kc, y_kc] {
                 for &lock in &lock_masks {
                     println!(
                         "Keycode = {kc}  , ModMask = {:?} (bits = 0x{:X})",
@@ -499,6 +521,7 @@ pub mod unix {
             conn.flush()?;
 
             let (tx, rx) = mpsc::channel();
+            let copy_translation_requested = self.copy_translation_requested.clone();
 
             println!("starting event loop thread");
             thread::spawn(move || {
@@ -528,6 +551,11 @@ pub mod unix {
 
                                 let _ = tx.send(FocusState::Unfocused);
                             }
+                            d if d == y_kc => {
+                                println!("Copy top translation");
+
+                                copy_translation_requested.store(true, Ordering::Relaxed);
+                            }
                             _ => {}
                         }
                     }
@@ -550,6 +578,10 @@ pub mod unix {
         fn current_focus_state(&self) -> FocusState {
             self.app_focus
         }
+
+        fn take_copy_translation_request(&mut self) -> bool {
+            self.copy_translation_requested.swap(false, Ordering::Relaxed)
+        }
     }
 
     // helper function to get the first x11 keycode matching a given keysym
@@ -583,6 +615,7 @@ pub mod unix {
     pub struct NativeViewportManagerWayland {
         app_focus: FocusState,
         focus_state_rx: Option<Receiver<FocusState>>,
+        copy_translation_requested: Arc<AtomicBool>, // set by the listener thread
         hotkey_daemon_handle: Option<Child>, // used for later shutdown
 
         winit_window: Arc<Window>,
@@ -599,6 +632,7 @@ pub mod unix {
     const DAEMON_FOCUS_EVENT: &str = "focus";
     const DAEMON_CLOSE_EVENT: &str = "close";
     const DAEMON_VISIBLE_EVENT: &str = "visible";
+    const DAEMON_COPY_TRANSLATION_EVENT: &str = "copy_translation";
 
 
     impl NativeViewportManagerWayland {
@@ -613,6 +647,7 @@ pub mod unix {
             let mut native_manager = Self {
                 app_focus: FocusState::Focused,
                 focus_state_rx: None,
+                copy_translation_requested: Arc::new(AtomicBool::new(false)),
                 hotkey_daemon_handle: None,
                 winit_window
             };
@@ -655,7 +690,7 @@ pub mod unix {
             // first notify user blocking, why the hotkey-daemon is requesting sudo rights
             if let Err(e) = Command::new("zenity").stdout(Stdio::null()).stderr(Stdio::null()).stdin(Stdio::null())
                 .arg("--info")
-                .arg("--text=On some linux desktop environments it is not possible to register global hotkeys. Wayland was detected as your DisplayCompositor, which prohibits global-hotkeys in general. One user-level approach would be to use the xdg-desktop-portal global hotkeys, but your desktop environment also didn't support it (e.g. GNOME DE supports it after version 48 (Ubuntu 25.04)). \nNow the only solution left for global hotkeys is a sudo key-reader (such programs can be security risk (key-loggers), so read my open source code and verify yourself). You will be prompted for sudo-privileges next. You can still deny the upcoming privileges request and use the app in a non-overlay way.")
+                .arg(format!("--text={}", crate::tr!(SUDO_EXPLANATION)))
                 .status() 
             { println!("Something went wrong, trying to inform the user of the upcoming sudo privileges prompt, because : {e}")};
 
@@ -737,6 +772,7 @@ pub mod unix {
             let (focus_update_tx, focus_update_rx) = mpsc::channel();
 
             let winit_window = self.winit_window.clone();
+            let copy_translation_requested = self.copy_translation_requested.clone();
             let socket_path = socket_path()?;
 
             thread::spawn(move || {
@@ -779,6 +815,10 @@ pub mod unix {
                                 // winit_window.set_window_level(winit::window::WindowLevel::AlwaysOnTop);
                                 let _ = focus_update_tx.send(FocusState::Unfocused);
                             }
+                            DAEMON_COPY_TRANSLATION_EVENT => {
+                                println!("Received: Copy top translation");
+                                copy_translation_requested.store(true, Ordering::Relaxed);
+                            }
                             _ => {}
                         },
                         Err(e) => {
@@ -830,6 +870,10 @@ pub mod unix {
             self.app_focus
         }
 
+        fn take_copy_translation_request(&mut self) -> bool {
+            self.copy_translation_requested.swap(false, Ordering::Relaxed)
+        }
+
         fn should_draw_gui(&self) -> bool {
             self.app_focus != FocusState::Hidden
         }
@@ -8,12 +8,25 @@ mod backend;
 mod frontend;
 mod utils;
 
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use app::{APP_ID, OverlayApp};
 use eframe::{CreationContext, NativeOptions, run_native};
 
 fn main() {
+    // guide authors can lint their resources without starting the overlay
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|arg| arg == "--check-resources") {
+        let dir = args
+            .get(pos + 1)
+            .filter(|arg| !arg.starts_with("--"))
+            .map(PathBuf::from);
+        // only on request, the app never contacts websites on its own
+        let check_web_links = args.iter().any(|arg| arg == "--check-web-links");
+        let exit_code = backend::resource_linter::run_check_resources(dir, check_web_links);
+        std::process::exit(exit_code);
+    }
+
     let icon_data = eframe::icon_data::from_png_bytes(include_bytes!(
         "../assets/icons/pokemmo-companion-main.png"
     ))
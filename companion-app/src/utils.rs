@@ -2,10 +2,12 @@
 
 use std::{
     fs,
-    io::{self, ErrorKind, Result},
-    path::PathBuf,
+    io::{self, ErrorKind, Result, Write},
+    path::{Path, PathBuf},
 };
 
+use crate::backend::network::NetworkAccess;
+
 pub fn find_asset_folder() -> io::Result<PathBuf> {
     const ASSETS_DIR_NAME: &str = "assets";
 
@@ -48,10 +50,20 @@ pub fn find_asset_folder() -> io::Result<PathBuf> {
     base_folder // bubble up error
 }
 
+/// reads every .md file in `path` and all its sub folders
+/// keys are the path relative to `path` without extension, separated by '/' (e.g. "Guides/Ho-Oh-Guide")
 pub fn read_in_all_markdown_files(path: PathBuf) -> Result<Vec<(String, String)>> {
     let mut md_file_list = Vec::new();
+    read_in_markdown_folder(&path, "", &mut md_file_list)?; // stop and let error bubble up
+    Ok(md_file_list)
+}
 
-    let read_dir = fs::read_dir(path)?; // stop and let error bubble up
+fn read_in_markdown_folder(
+    path: &PathBuf,
+    key_prefix: &str,
+    md_file_list: &mut Vec<(String, String)>,
+) -> Result<()> {
+    let read_dir = fs::read_dir(path)?;
 
     for entry in read_dir {
         if entry.is_err() {
@@ -60,7 +72,15 @@ pub fn read_in_all_markdown_files(path: PathBuf) -> Result<Vec<(String, String)>
         }
         let path = entry?.path();
 
-        if path.is_file()
+        if path.is_dir() {
+            if let Some(folder_name) = path.file_name().and_then(|os| os.to_str()) {
+                let sub_prefix = format!("{key_prefix}{folder_name}/");
+                if let Err(e) = read_in_markdown_folder(&path, &sub_prefix, md_file_list) {
+                    // a broken sub folder shouldn't take down all the other resources
+                    eprintln!("Utils - couldn't read resource folder {path:?}: {e}");
+                }
+            }
+        } else if path.is_file()
             && let Some(ext) = path.extension().and_then(|s| s.to_str())
             && ext.eq_ignore_ascii_case("md")
         {
@@ -68,11 +88,31 @@ pub fn read_in_all_markdown_files(path: PathBuf) -> Result<Vec<(String, String)>
             let contents = fs::read_to_string(&path).unwrap_or_else(|e| e.to_string());
             // only add resource if filename(used as identifier) is valid, ignore otherwise
             if let Some(stem) = path.file_stem().and_then(|os| os.to_str()) {
-                md_file_list.push((stem.to_owned(), contents));
+                md_file_list.push((format!("{key_prefix}{stem}"), contents));
             }
         }
     }
-    Ok(md_file_list)
+    Ok(())
+}
+
+/// writes to a temporary file next to `dest` first and renames it into place afterwards,
+/// so a crash mid-write never leaves a half written file behind
+pub fn write_file_atomic(dest: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp = dest.with_extension("tmp");
+    let mut file = fs::File::create(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?; // make sure it's on disk before replacing the original
+    drop(file);
+
+    if let Err(e) = fs::rename(&tmp, dest) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    Ok(())
 }
 
 #[allow(dead_code)]
@@ -108,32 +148,103 @@ pub fn convert_cyrillic_string(input: &str) -> String {
 /// - overrides any existing file at the given destination
 /// - creates any directories not present towards the destination path
 /// - returns a reqwest error when URL is bad (e.g. reqwest::StatusCode::BAD_REQUEST)
-pub async fn download_to_path(url: &str, dest: PathBuf) -> anyhow::Result<PathBuf> {
-    // Use reqwest for HTTP; stream to tokio::fs file to avoid allocating entire body in memory.
-    let client = reqwest::Client::new();
-    let mut resp = client.get(url).send().await?.error_for_status()?;
+/// - fails without touching the network when the network policy doesn't allow it
+#[allow(dead_code)]
+pub async fn download_to_path(
+    network: &NetworkAccess,
+    url: &str,
+    dest: PathBuf,
+) -> anyhow::Result<PathBuf> {
+    download_checked(network, url, dest, None).await
+}
 
-    // create parent dirs
-    if let Some(parent) = dest.parent() {
-        tokio::fs::create_dir_all(parent).await?;
-    }
+/// like download_to_path, but the download only replaces `dest` if it has exactly the expected
+/// size and sha256 (lowercase hex), otherwise the old file stays untouched
+pub async fn download_to_path_verified(
+    network: &NetworkAccess,
+    url: &str,
+    dest: PathBuf,
+    expected_size: u64,
+    expected_sha256: &str,
+) -> anyhow::Result<PathBuf> {
+    download_checked(network, url, dest, Some((expected_size, expected_sha256))).await
+}
 
+async fn download_checked(
+    network: &NetworkAccess,
+    url: &str,
+    dest: PathBuf,
+    expected: Option<(u64, &str)>,
+) -> anyhow::Result<PathBuf> {
     let tmp = dest.with_extension("tmp");
-    let mut file = tokio::fs::File::create(&tmp).await?;
+    match download_via_tmp(network, url, &tmp, &dest, expected).await {
+        Ok(()) => Ok(dest),
+        Err(e) => {
+            // whatever failed (network, disk, verification), no half written file stays behind
+            let _ = tokio::fs::remove_file(&tmp).await;
+            Err(e)
+        }
+    }
+}
+
+async fn download_via_tmp(
+    network: &NetworkAccess,
+    url: &str,
+    tmp: &Path,
+    dest: &Path,
+    expected: Option<(u64, &str)>,
+) -> anyhow::Result<()> {
+    // stream to tokio::fs file to avoid allocating entire body in memory.
+    // a retry after a broken off download starts over with a fresh file
+    let (tmp_ref, dest_ref) = (tmp, dest);
+    let (size, sha256) = network
+        .get(url, |mut resp| async move {
+            use sha2::Digest;
+            use tokio::io::AsyncWriteExt;
+            // create parent dirs
+            if let Some(parent) = dest_ref.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let mut file = tokio::fs::File::create(tmp_ref).await?;
+            let mut hasher = sha2::Sha256::new();
+            let mut size: u64 = 0;
+            while let Some(chunk) = resp.chunk().await? {
+                hasher.update(&chunk);
+                size += chunk.len() as u64;
+                file.write_all(&chunk).await?;
+            }
+            // make sure to finish writing
+            file.sync_all().await?;
+            file.flush().await?;
+            Ok((size, format!("{:x}", hasher.finalize())))
+        })
+        .await?;
 
-    use tokio::io::AsyncWriteExt;
-    while let Some(chunk) = resp.chunk().await? {
-        file.write_all(&chunk).await?;
+    // verify before anything is replaced
+    if let Some((expected_size, expected_sha256)) = expected
+        && (size != expected_size || sha256 != expected_sha256)
+    {
+        anyhow::bail!(
+            "{url} failed verification (got {size} bytes / sha256 {sha256}, expected {expected_size} bytes / sha256 {expected_sha256})"
+        );
     }
-    // make sure to finish writing
-    file.sync_all().await?;
-    file.flush().await?;
 
     // Windows: remove existing file first to avoid rename failure
-    let _ = tokio::fs::remove_file(&dest).await;
+    let _ = tokio::fs::remove_file(dest).await;
 
     // commit file download by renaming into place
-    tokio::fs::rename(&tmp, &dest).await?;
+    tokio::fs::rename(tmp, dest).await?;
+
+    Ok(())
+}
+
+/// lowercase hex sha256, the same format locale manifests use
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::Digest;
+    format!("{:x}", sha2::Sha256::digest(bytes))
+}
 
-    Ok(dest)
+/// fetches a small text file (e.g. a manifest) without touching the disk
+pub async fn fetch_text(network: &NetworkAccess, url: &str) -> anyhow::Result<String> {
+    network.get(url, async |resp| Ok(resp.text().await?)).await
 }
@@ -3,3 +3,12 @@ set -euo pipefail
 
 cp base/* ../../companion-app/assets/data/
 cp -r locales/* ../../companion-app/assets/locales/
+../../target/debug/data-builder bundle ../../companion-app/assets/locales/
+../../target/debug/data-builder manifest ../../companion-app/assets/locales/
+
+# sprites of the data cards, monsters only need their first icon frame ("<dex-id>-0.png")
+mkdir -p ../../companion-app/assets/sprites/pokemon ../../companion-app/assets/sprites/items
+for icon in icons/monster/*-0.png; do
+    cp "$icon" "../../companion-app/assets/sprites/pokemon/$(basename "$icon" -0.png).png"
+done
+cp icons/items/*.png ../../companion-app/assets/sprites/items/
@@ -0,0 +1,31 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::coverage::LOCALE_FILES;
+use locale_data::bundle::{BUNDLE_FILE, build_bundle};
+
+/// writes a locale.bundle next to the json files of every locale in `locales_dir`
+pub fn write_bundles(locales_dir: &Path) -> anyhow::Result<()> {
+    let mut locale_dirs: Vec<PathBuf> = fs::read_dir(locales_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.join(LOCALE_FILES[0].0).exists())
+        .collect();
+    locale_dirs.sort();
+
+    for locale_dir in locale_dirs {
+        let bundle = build_bundle(&locale_dir)?;
+        let path = locale_dir.join(BUNDLE_FILE);
+        let bytes = bincode::serialize(&bundle)?;
+        println!(
+            "Writing locale bundle ({} texts, {} KiB) to disk : {:?}",
+            bundle.files.iter().map(|f| f.entries.len()).sum::<usize>(),
+            bytes.len() / 1024,
+            path
+        );
+        fs::write(path, bytes)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use indexmap::IndexMap;
+use locale_data::text_coverage::compare_texts;
+
+use crate::utils::{parse_json_items_from_file_to_index_map, validate_dir};
+
+// built locale file -> category name used in the report
+pub const LOCALE_FILES: &[(&str, &str)] = &[
+    ("monsters.json", "Pokémon"),
+    ("skills.json", "Moves"),
+    ("locations.json", "Locations"),
+    ("locations_pokedex.json", "Pokédex Locations"),
+    ("item_names.json", "Items"),
+    ("item_descriptions.json", "Item Descriptions"),
+    ("miscellaneous.json", "Misc"),
+];
+
+/// Prints per locale and category how complete the built locale files in `locales_dir` are,
+/// compared to the `base_locale` (which is built from the base data)
+pub fn print_coverage_report(
+    locales_dir: &Path,
+    base_locale: &str,
+    list_keys: bool,
+) -> anyhow::Result<()> {
+    let file_names: Vec<&str> = LOCALE_FILES.iter().map(|(file, _)| *file).collect();
+    let base_dir = locales_dir.join(base_locale);
+    validate_dir(&base_dir, "Base Locale Directory", &file_names)?;
+
+    let mut locale_dirs: Vec<PathBuf> = fs::read_dir(locales_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    locale_dirs.sort();
+
+    let base_files: Vec<IndexMap<String, String>> = LOCALE_FILES
+        .iter()
+        .map(|(file, _)| parse_json_items_from_file_to_index_map(&base_dir.join(file)))
+        .collect();
+
+    let mut summary = Vec::new();
+    for locale_dir in locale_dirs {
+        let locale = locale_dir
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        println!("\n---------- {locale} ----------");
+
+        let (mut base_total, mut translated_total, mut issues_total) = (0, 0, 0);
+        for ((file, category), base_texts) in LOCALE_FILES.iter().zip(&base_files) {
+            let texts =
+                parse_json_items_from_file_to_index_map::<String, String>(&locale_dir.join(file));
+            let coverage = compare_texts(
+                &as_text_map(base_texts),
+                &as_text_map(&texts),
+                locale == base_locale,
+            );
+
+            println!(
+                "{category:<18} {:>5}/{:<5} missing: {}, empty: {}, same as {base_locale}: {}, not in base data: {}",
+                coverage.translated_count(),
+                coverage.base_count,
+                coverage.missing.len(),
+                coverage.empty.len(),
+                coverage.same_as_base.len(),
+                coverage.unknown.len(),
+            );
+            if list_keys {
+                print_keys("missing", &coverage.missing);
+                print_keys("empty", &coverage.empty);
+                print_keys(&format!("same as {base_locale}"), &coverage.same_as_base);
+                print_keys("not in base data", &coverage.unknown);
+            }
+
+            base_total += coverage.base_count;
+            translated_total += coverage.translated_count();
+            issues_total += coverage.issue_count();
+        }
+        summary.push((locale, translated_total, base_total, issues_total));
+    }
+
+    println!("\n---------- Summary ----------");
+    for (locale, translated, total, issues) in summary {
+        let percent = match total {
+            0 => 100.,
+            n => translated as f32 / n as f32 * 100.,
+        };
+        println!("{locale:<4} {percent:>6.2}% ({translated}/{total}), {issues} to check");
+    }
+
+    Ok(())
+}
+
+fn print_keys(label: &str, keys: &[String]) {
+    if keys.is_empty() {
+        return;
+    }
+    println!("    {label}:");
+    for key in keys {
+        println!("        {key}");
+    }
+}
+
+fn as_text_map(texts: &IndexMap<String, String>) -> HashMap<&str, &str> {
+    texts
+        .iter()
+        .map(|(key, text)| (key.as_str(), text.as_str()))
+        .collect()
+}
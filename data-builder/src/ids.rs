@@ -0,0 +1,160 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    generic_entry::Entry,
+    items::Item,
+    miscellaneous, monsters,
+    utils::{
+        normalize_name, parse_json_items_from_file, parse_json_items_from_file_to_index_map,
+        parse_string_items_from_xml_file, validate_dir,
+    },
+};
+use indexmap::IndexMap;
+use locale_data::{
+    ids::{IDS_FILE, LocaleIds},
+    locale::TextCategory,
+};
+
+/// adds the (id, key) pairs of one category, skipping keys the built lookup doesn't have
+pub fn insert_ids(
+    ids: &mut LocaleIds,
+    category: TextCategory,
+    id_keys: impl IntoIterator<Item = (u32, String)>,
+    lookup: &IndexMap<String, String>,
+) {
+    let category_ids = ids.entry(category).or_default();
+    for (id, key) in id_keys {
+        if lookup.contains_key(&key) {
+            category_ids.insert(id, key);
+        }
+    }
+}
+
+/// writes ids.json into every built locale in `locales_dir` (e.g. the app's assets/locales/)
+pub fn write_all_locale_ids(en_dir: &Path, locales_dir: &Path) -> anyhow::Result<()> {
+    let mut locale_dirs: Vec<PathBuf> = fs::read_dir(locales_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.join("monsters.json").exists())
+        .collect();
+    locale_dirs.sort();
+
+    for locale_dir in locale_dirs {
+        write_locale_ids(en_dir, &locale_dir)?;
+    }
+    Ok(())
+}
+
+/// the ids only need the english dumps, every id whose key the built locale files in
+/// `locale_dir` have ends up in its ids.json
+/// monsters use their dex id from the string dump, the same as the monster base data
+pub fn write_locale_ids(en_dir: &Path, locale_dir: &Path) -> anyhow::Result<()> {
+    validate_dir(
+        en_dir,
+        "EN Directory",
+        &["items.json", "skills.json", "dump_strings.xml"],
+    )?;
+    let lookup = |file: &str| {
+        parse_json_items_from_file_to_index_map::<String, String>(&locale_dir.join(file))
+    };
+    let mut ids = LocaleIds::new();
+
+    let en_string_dump = parse_string_items_from_xml_file(&en_dir.join("dump_strings.xml"));
+    let monster_ids = monsters::monster_names(&en_string_dump)
+        .into_iter()
+        .map(|(id, name)| (id, normalize_name(name.clone())));
+    insert_ids(
+        &mut ids,
+        TextCategory::Monster,
+        monster_ids,
+        &lookup("monsters.json"),
+    );
+
+    let en_items = parse_json_items_from_file::<Item>(&en_dir.join("items.json"));
+    let name_ids = en_items
+        .iter()
+        .map(|i| (i.id, normalize_name(i.name.clone())));
+    insert_ids(
+        &mut ids,
+        TextCategory::Item,
+        name_ids,
+        &lookup("item_names.json"),
+    );
+    let desc_ids = en_items
+        .iter()
+        .map(|i| (i.id, normalize_name(i.desc.clone())));
+    insert_ids(
+        &mut ids,
+        TextCategory::ItemDescription,
+        desc_ids,
+        &lookup("item_descriptions.json"),
+    );
+
+    let en_skills = parse_json_items_from_file::<Entry>(&en_dir.join("skills.json"));
+    let move_ids = en_skills
+        .iter()
+        .map(|e| (e.id, normalize_name(e.name.clone())));
+    insert_ids(
+        &mut ids,
+        TextCategory::Move,
+        move_ids,
+        &lookup("skills.json"),
+    );
+
+    let misc_ids = miscellaneous::build_miscellaneous_ids(&en_string_dump);
+    insert_ids(
+        &mut ids,
+        TextCategory::Miscellaneous,
+        misc_ids,
+        &lookup("miscellaneous.json"),
+    );
+
+    // locations have no ids of their own (they're built from the encounter data), names only
+    write_ids_to_disk(&ids, &locale_dir.join(IDS_FILE))
+}
+
+fn write_ids_to_disk(ids: &LocaleIds, path: &Path) -> anyhow::Result<()> {
+    println!(
+        "Writing locale ids ({} ids) to disk : {:?}",
+        ids.values().map(|c| c.len()).sum::<usize>(),
+        path
+    );
+    serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), ids)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_ids() {
+        let mut ids = LocaleIds::new();
+        let lookup = IndexMap::from([("metronome".to_owned(), "Metronom".to_owned())]);
+        insert_ids(
+            &mut ids,
+            TextCategory::Move,
+            [(118, "metronome".to_owned()), (999, "unknown".to_owned())],
+            &lookup,
+        );
+        insert_ids(
+            &mut ids,
+            TextCategory::Item,
+            [(277, "metronome".to_owned())],
+            &lookup,
+        );
+
+        // same key, but still two distinct texts
+        assert_eq!(
+            serde_json::to_value(&ids).unwrap(),
+            serde_json::json!({
+                "Item": { "277": "metronome" },
+                "Move": { "118": "metronome" },
+            })
+        );
+    }
+}
@@ -3,9 +3,9 @@ use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
 pub struct Item {
-    id: u32,
-    name: String,
-    desc: String,
+    pub id: u32,
+    pub name: String,
+    pub desc: String,
 }
 
 pub fn build_item_locale_lookups(
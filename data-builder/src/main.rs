@@ -1,33 +1,50 @@
+mod bundle;
+mod coverage;
 mod generic_entry;
+mod ids;
 mod items;
 mod locations;
+mod manifest;
 mod miscellaneous;
+mod monsters;
 mod utils;
 
-use clap::{ArgAction, Parser};
+use clap::{ArgAction, Parser, Subcommand};
 use generic_entry::Entry;
 use indexmap::IndexMap;
 use items::Item;
 use locations::MonsterLocations;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use utils::{
     parse_json_items_from_file, parse_json_items_from_file_to_index_map,
     parse_string_items_from_xml_file, validate_dir, write_locale_lookup_to_disk,
 };
 
 /// Example call: ./data-builder "EN_DIR" "DE_DIR" --out "OUT_DIR"
+/// or for a report of the built locales: ./data-builder coverage "LOCALES_DIR"
+/// or for the app's locale update manifest: ./data-builder manifest "APP_LOCALES_DIR"
+/// or for the app's precompiled locales: ./data-builder bundle "APP_LOCALES_DIR"
+/// or for the ids of already built locales: ./data-builder ids "EN_DIR" "APP_LOCALES_DIR"
+/// or for the monster base data only: ./data-builder monsters "EN_DIR" --out "BASE_DATA_DIR"
 #[derive(Parser, Debug)]
 #[command(
     author,
     version,
-    about = "After validating both directories exist and contain valid files it will try to build the locale lookup files by diff-ing the source locale_dir with the source en_dir + some additional magic"
+    about = "After validating both directories exist and contain valid files it will try to build the locale lookup files by diff-ing the source locale_dir with the source en_dir + some additional magic",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 struct CLIArgs {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the EN directory (required)
-    en_dir: PathBuf,
+    #[arg(required = true)]
+    en_dir: Option<PathBuf>,
 
     /// Path to the new locale directory (required)
-    locale_dir: PathBuf,
+    #[arg(required = true)]
+    locale_dir: Option<PathBuf>,
 
     /// Optional output directory
     #[arg(short, long)]
@@ -41,12 +58,83 @@ struct CLIArgs {
     normal_case_base_data: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Reports how complete each built locale is (per category), compared to the base locale
+    Coverage {
+        /// Directory containing one directory per built locale (e.g. data/locales/)
+        locales_dir: PathBuf,
+
+        /// Locale built from the base data, which the others are compared to
+        #[arg(short, long, default_value = "EN")]
+        base_locale: String,
+
+        /// Also list every flagged key, not only how many there are
+        #[arg(short, long, action = ArgAction::SetTrue)]
+        list_keys: bool,
+    },
+    /// Writes a precompiled locale.bundle next to the json files of every locale (faster app startup)
+    Bundle {
+        /// Directory containing one directory per built locale (e.g. ../companion-app/assets/locales/)
+        locales_dir: PathBuf,
+    },
+    /// Writes ids.json (category -> stable id -> key) into every built locale, only needs the english dumps
+    Ids {
+        /// Path to the EN directory (items.json, skills.json, dump_strings.xml)
+        en_dir: PathBuf,
+
+        /// Directory containing one directory per built locale (e.g. ../companion-app/assets/locales/)
+        locales_dir: PathBuf,
+    },
+    /// Writes the monster base data (dex id, name, types), which only needs the english string dump
+    Monsters {
+        /// Path to the EN directory (dump_strings.xml + monster_types.json)
+        en_dir: PathBuf,
+
+        /// Base data output directory (e.g. data/base/)
+        #[arg(short, long)]
+        out: PathBuf,
+
+        #[arg(short, long, action = ArgAction::SetTrue)]
+        normal_case_base_data: bool,
+    },
+    /// Writes locale_manifest.json (size + sha256 of every file), which the app updates against
+    Manifest {
+        /// Directory the app downloads locales from (e.g. ../companion-app/assets/locales/)
+        locales_dir: PathBuf,
+    },
+}
+
 fn main() -> anyhow::Result<()> {
     let args = CLIArgs::parse();
 
+    match &args.command {
+        Some(Command::Coverage {
+            locales_dir,
+            base_locale,
+            list_keys,
+        }) => return coverage::print_coverage_report(locales_dir, base_locale, *list_keys),
+        Some(Command::Bundle { locales_dir }) => return bundle::write_bundles(locales_dir),
+        Some(Command::Manifest { locales_dir }) => return manifest::write_manifest(locales_dir),
+        Some(Command::Ids {
+            en_dir,
+            locales_dir,
+        }) => return ids::write_all_locale_ids(en_dir, locales_dir),
+        Some(Command::Monsters {
+            en_dir,
+            out,
+            normal_case_base_data,
+        }) => return write_monster_base_data(en_dir, out, *normal_case_base_data),
+        None => (),
+    }
+    // both are required without a subcommand
+    let (Some(en_dir), Some(locale_dir)) = (args.en_dir, args.locale_dir) else {
+        unreachable!("clap enforces both directories");
+    };
+
     println!(
         "-------------------------------------------------------------------\nStarting data-builder...\nLooking for source data at :\n    {:?}\n    and\n    {:?}",
-        args.en_dir, args.locale_dir
+        en_dir, locale_dir
     );
 
     const REQUIRED_FILES: &[&str] = &[
@@ -55,8 +143,8 @@ fn main() -> anyhow::Result<()> {
         "skills.json",
         "dump_strings.xml",
     ];
-    validate_dir(&args.en_dir, "EN Directory", REQUIRED_FILES)?;
-    validate_dir(&args.locale_dir, "Locale Directory", REQUIRED_FILES)?;
+    validate_dir(&en_dir, "EN Directory", REQUIRED_FILES)?;
+    validate_dir(&locale_dir, "Locale Directory", REQUIRED_FILES)?;
 
     let out_dir = args.out.unwrap_or(
         std::env::current_dir()
@@ -66,10 +154,9 @@ fn main() -> anyhow::Result<()> {
 
     println!("\n---------- Monsters ----------");
     {
-        let mut en_entries =
-            parse_json_items_from_file::<Entry>(&args.en_dir.join("monsters.json"));
+        let mut en_entries = parse_json_items_from_file::<Entry>(&en_dir.join("monsters.json"));
         let mut locale_entries =
-            parse_json_items_from_file::<Entry>(&args.locale_dir.join("monsters.json"));
+            parse_json_items_from_file::<Entry>(&locale_dir.join("monsters.json"));
         // id's over 1000 are event specifics and stuff -> remove
         en_entries.retain(|e| e.id < 1000);
         locale_entries.retain(|e| e.id < 1000);
@@ -79,8 +166,8 @@ fn main() -> anyhow::Result<()> {
 
     println!("\n---------- ITEMS -------------");
     {
-        let en_items = parse_json_items_from_file::<Item>(&args.en_dir.join("items.json"));
-        let locale_items = parse_json_items_from_file::<Item>(&args.locale_dir.join("items.json"));
+        let en_items = parse_json_items_from_file::<Item>(&en_dir.join("items.json"));
+        let locale_items = parse_json_items_from_file::<Item>(&locale_dir.join("items.json"));
         let (name_lookup, desc_lookup) = items::build_item_locale_lookups(en_items, locale_items);
         write_locale_lookup_to_disk(name_lookup, out_dir.clone().join("item_names.json"))?;
         write_locale_lookup_to_disk(desc_lookup, out_dir.clone().join("item_descriptions.json"))?;
@@ -88,9 +175,8 @@ fn main() -> anyhow::Result<()> {
 
     println!("\n---------- Skills ----------");
     {
-        let en_entries = parse_json_items_from_file::<Entry>(&args.en_dir.join("skills.json"));
-        let locale_entries =
-            parse_json_items_from_file::<Entry>(&args.locale_dir.join("skills.json"));
+        let en_entries = parse_json_items_from_file::<Entry>(&en_dir.join("skills.json"));
+        let locale_entries = parse_json_items_from_file::<Entry>(&locale_dir.join("skills.json"));
         let name_lookup = generic_entry::build_generic_locale_lookup(en_entries, locale_entries);
         write_locale_lookup_to_disk(name_lookup, out_dir.clone().join("skills.json"))?;
     }
@@ -98,14 +184,13 @@ fn main() -> anyhow::Result<()> {
     println!("\n---------- Locations ----------");
     {
         let en_monster_locations =
-            parse_json_items_from_file::<MonsterLocations>(&args.en_dir.join("monsters.json"));
-        let en_string_dump =
-            parse_string_items_from_xml_file(&args.en_dir.join("dump_strings.xml"));
+            parse_json_items_from_file::<MonsterLocations>(&en_dir.join("monsters.json"));
+        let en_string_dump = parse_string_items_from_xml_file(&en_dir.join("dump_strings.xml"));
         let locale_string_dump =
-            parse_string_items_from_xml_file(&args.locale_dir.join("dump_strings.xml"));
+            parse_string_items_from_xml_file(&locale_dir.join("dump_strings.xml"));
         let locale_additional_translations: IndexMap<String, String> =
             parse_json_items_from_file_to_index_map::<String, String>(
-                &args.locale_dir.join("additional_translations.json"),
+                &locale_dir.join("additional_translations.json"),
             );
         let (unique_location_lookup, pokedex_location_lookup) =
             locations::build_location_locale_lookup(
@@ -127,14 +212,13 @@ fn main() -> anyhow::Result<()> {
     println!("\n---------- Miscellaneous ----------");
     {
         // let en_monster_locations =
-        //     parse_json_items_from_file::<MonsterLocations>(&args.en_dir.join("monsters.json"));
-        let en_string_dump =
-            parse_string_items_from_xml_file(&args.en_dir.join("dump_strings.xml"));
+        //     parse_json_items_from_file::<MonsterLocations>(&en_dir.join("monsters.json"));
+        let en_string_dump = parse_string_items_from_xml_file(&en_dir.join("dump_strings.xml"));
         let locale_string_dump =
-            parse_string_items_from_xml_file(&args.locale_dir.join("dump_strings.xml"));
+            parse_string_items_from_xml_file(&locale_dir.join("dump_strings.xml"));
         let locale_additional_translations: IndexMap<String, String> =
             parse_json_items_from_file_to_index_map::<String, String>(
-                &args.locale_dir.join("additional_translations.json"),
+                &locale_dir.join("additional_translations.json"),
             );
         let miscellaneous_locale_lookup = miscellaneous::build_miscellaneous_locale_lookup(
             en_string_dump,
@@ -147,10 +231,13 @@ fn main() -> anyhow::Result<()> {
         )?;
     }
 
+    println!("\n---------- Ids ----------");
+    ids::write_locale_ids(&en_dir, &out_dir)?;
+
     if let Some(base_data_out) = &args.base_data_out {
         println!("\n---------- Base Data ----------");
         let en_monster_locations =
-            parse_json_items_from_file::<MonsterLocations>(&args.en_dir.join("monsters.json"));
+            parse_json_items_from_file::<MonsterLocations>(&en_dir.join("monsters.json"));
         let location_base_data = locations::build_location_base_data(en_monster_locations);
         utils::write_base_data_to_disk(location_base_data, base_data_out.join("locations.json"))?;
         // hack to make all keys lower_case() for easier lookup into locales
@@ -159,13 +246,14 @@ fn main() -> anyhow::Result<()> {
             utils::rewrite_file_lower_case(&base_data_out.join("locations.json"))?;
         }
 
+        // the pokedex monster dump is mostly encounters (already in locations.json), so monsters
+        // get their own compact file instead of a copy
+        write_monster_base_data(&en_dir, base_data_out, args.normal_case_base_data)?;
+
         // copy pokedex dump date to base-data directory
-        const POKEDEX_DUMP_FILES: &[&str] = &["monsters.json", "items.json", "skills.json"];
+        const POKEDEX_DUMP_FILES: &[&str] = &["items.json", "skills.json"];
         for filename in POKEDEX_DUMP_FILES {
-            utils::copy_file_overwriting(
-                &args.en_dir.join(filename),
-                &base_data_out.join(filename),
-            )?;
+            utils::copy_file_overwriting(&en_dir.join(filename), &base_data_out.join(filename))?;
 
             // same hack for lowercase keys
             if !args.normal_case_base_data {
@@ -176,3 +264,28 @@ fn main() -> anyhow::Result<()> {
     println!("\n-------------------------------------------------------------------\nFinished !");
     Ok(())
 }
+
+fn write_monster_base_data(
+    en_dir: &Path,
+    base_data_out: &Path,
+    normal_case_base_data: bool,
+) -> anyhow::Result<()> {
+    validate_dir(
+        en_dir,
+        "EN Directory",
+        &["dump_strings.xml", monsters::MONSTER_TYPES_FILE],
+    )?;
+    let en_string_dump = parse_string_items_from_xml_file(&en_dir.join("dump_strings.xml"));
+    let monster_types = parse_json_items_from_file_to_index_map::<u32, Vec<String>>(
+        &en_dir.join(monsters::MONSTER_TYPES_FILE),
+    );
+    let monster_base_data = monsters::build_monster_base_data(&en_string_dump, &monster_types);
+
+    let monsters_file = base_data_out.join("monsters.json");
+    utils::write_base_data_to_disk(monster_base_data, monsters_file.clone())?;
+    // same hack for lowercase keys
+    if !normal_case_base_data {
+        utils::rewrite_file_lower_case(&monsters_file)?;
+    }
+    Ok(())
+}
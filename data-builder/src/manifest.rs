@@ -0,0 +1,118 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use locale_data::bundle::BUNDLE_FILE;
+
+/// has to match the companion app's locale_manifest module
+pub const MANIFEST_FILE: &str = "locale_manifest.json";
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ManifestEntry {
+    pub size: u64,
+    pub sha256: String, // lowercase hex
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct LocaleManifest {
+    pub files: BTreeMap<String, ManifestEntry>, // "/" separated path relative to the locales dir
+}
+
+/// size and sha256 of every file in `locales_dir` (recursively), the app only downloads
+/// files whose entry differs from its local copy
+pub fn build_manifest(locales_dir: &Path) -> anyhow::Result<LocaleManifest> {
+    let mut manifest = LocaleManifest::default();
+    for path in collect_files(locales_dir)? {
+        let relative = path.strip_prefix(locales_dir)?;
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        // bundles are built for releases, locale updates only bring the json files
+        if name == MANIFEST_FILE || name.ends_with(BUNDLE_FILE) {
+            continue;
+        }
+
+        let content = fs::read(&path)?;
+        manifest.files.insert(
+            name,
+            ManifestEntry {
+                size: content.len() as u64,
+                sha256: format!("{:x}", Sha256::digest(&content)),
+            },
+        );
+    }
+    Ok(manifest)
+}
+
+pub fn write_manifest(locales_dir: &Path) -> anyhow::Result<()> {
+    let manifest = build_manifest(locales_dir)?;
+    let path = locales_dir.join(MANIFEST_FILE);
+    println!(
+        "Writing locale manifest ({} files) to disk : {:?}",
+        manifest.files.len(),
+        path
+    );
+    fs::write(path, serde_json::to_string_pretty(&manifest)? + "\n")?;
+    Ok(())
+}
+
+// skips hidden entries, e.g. the app's .staging/.backup dirs of an interrupted update
+fn collect_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        {
+            continue;
+        }
+        if path.is_dir() {
+            files.extend(collect_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_manifest() {
+        let dir =
+            std::env::temp_dir().join(format!("data_builder_manifest_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("DE")).unwrap();
+        fs::create_dir_all(dir.join(".staging/DE")).unwrap();
+        fs::write(dir.join("locale_definition.json"), "{}").unwrap();
+        fs::write(dir.join("DE/monsters.json"), "abc").unwrap();
+        fs::write(dir.join("DE/locale.bundle"), "bundle").unwrap();
+        fs::write(dir.join(".staging/DE/monsters.json"), "half").unwrap();
+        fs::write(dir.join(MANIFEST_FILE), "old").unwrap();
+
+        let manifest = build_manifest(&dir).unwrap();
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            vec!["DE/monsters.json", "locale_definition.json"]
+        );
+        assert_eq!(
+            manifest.files["DE/monsters.json"],
+            ManifestEntry {
+                size: 3,
+                sha256: "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".into()
+            }
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -154,6 +154,21 @@ pub fn build_miscellaneous_locale_lookup(
     translations
 }
 
+/// (string id, key) of every translated string, the string id is what the game itself uses
+pub fn build_miscellaneous_ids(en_string_dump: &IndexMap<u32, String>) -> Vec<(u32, String)> {
+    let mut ids = Vec::new();
+    for (first, last) in IDS_TO_TRANSLATE {
+        for i in *first..=*last {
+            if let Some(english) = en_string_dump.get(&i)
+                && let Ok(key) = resolve_string_dump_references(english, en_string_dump)
+            {
+                ids.push((i, normalize_name(key)));
+            }
+        }
+    }
+    ids
+}
+
 fn resolve_string_dump_references(
     string: &String,
     string_dump: &IndexMap<u32, String>,
@@ -0,0 +1,96 @@
+use indexmap::IndexMap;
+use serde::Serialize;
+
+// the string dump holds every monster name at 150000 + dex id
+//
+// <string id="150001">Bulbasaur</string>
+// <string id="150002">Ivysaur</string>
+// ...
+// <string id="150649">Genesect</string>
+// <string id="150650">Egg</string>   <- eggs & alternate forms follow, those aren't own monsters
+const MONSTER_NAME_STRING_OFFSET: u32 = 150000;
+const LAST_DEX_ID: u32 = 649;
+
+/// language independent, hand maintained (dex id -> english type names), the string dump has no types
+/// gen 5 typings, like PokeMMO (no fairy)
+pub const MONSTER_TYPES_FILE: &str = "monster_types.json";
+
+/// one monster of the base data (assets/data/monsters.json in the app)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct MonsterInfo {
+    pub id: u32,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+/// (dex id, english name) of every monster in the string dump, in dex order
+pub fn monster_names(en_string_dump: &IndexMap<u32, String>) -> Vec<(u32, &String)> {
+    (1..=LAST_DEX_ID)
+        .filter_map(
+            |id| match en_string_dump.get(&(MONSTER_NAME_STRING_OFFSET + id)) {
+                Some(name) => Some((id, name)),
+                None => {
+                    println!("no name for monster {id} in the string dump, skipping it");
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+/// dex ids and names come from the string dump, so ids never depend on the order of any file
+pub fn build_monster_base_data(
+    en_string_dump: &IndexMap<u32, String>,
+    monster_types: &IndexMap<u32, Vec<String>>,
+) -> Vec<MonsterInfo> {
+    println!("Building monster base data...");
+    let mut monsters = Vec::new();
+    for (id, name) in monster_names(en_string_dump) {
+        let types = monster_types.get(&id).cloned().unwrap_or_else(|| {
+            println!("no types for monster {id} ({name}) in {MONSTER_TYPES_FILE}");
+            Vec::new()
+        });
+        monsters.push(MonsterInfo {
+            id,
+            name: name.clone(),
+            types,
+        });
+    }
+    monsters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_monster_base_data() {
+        let string_dump = IndexMap::from([
+            (150000, "null".to_owned()),
+            (150445, "Garchomp".to_owned()),
+            (150001, "Bulbasaur".to_owned()),
+            (150650, "Egg".to_owned()),
+        ]);
+        let types = IndexMap::from([
+            (1, vec!["Grass".to_owned(), "Poison".to_owned()]),
+            (445, vec!["Dragon".to_owned(), "Ground".to_owned()]),
+        ]);
+
+        // dex order, whatever order the dump was in, eggs & "null" are left out
+        assert_eq!(
+            build_monster_base_data(&string_dump, &types),
+            vec![
+                MonsterInfo {
+                    id: 1,
+                    name: "Bulbasaur".to_owned(),
+                    types: vec!["Grass".to_owned(), "Poison".to_owned()],
+                },
+                MonsterInfo {
+                    id: 445,
+                    name: "Garchomp".to_owned(),
+                    types: vec!["Dragon".to_owned(), "Ground".to_owned()],
+                },
+            ]
+        );
+    }
+}
@@ -38,6 +38,7 @@ pub mod platform {
     const FOCUS: &str = "focus";
     const CLOSE: &str = "close";
     const VISIBLE: &str = "visible";
+    const COPY_TRANSLATION: &str = "copy_translation";
 
     pub fn main() {
         match start_hotkey_daemon() {
@@ -87,7 +88,7 @@ pub mod platform {
         }
 
         println!("\nhotkey-daemon: Start listening for hotkey combinations...");
-        // listen on each device and handle alt + f/c/v keycodes
+        // listen on each device and handle alt + f/c/v/y keycodes
         let mut alt_down = false;
         loop {
             for dev in &mut devs {
@@ -115,6 +116,10 @@ pub mod platform {
                                     println!("hotkey-daemon: Alt+V pressed → notifying clients");
                                     notify_clients_of(&clients, VISIBLE);
                                 }
+                                (KeyCode::KEY_Y, 1) if alt_down => {
+                                    println!("hotkey-daemon: Alt+Y pressed → notifying clients");
+                                    notify_clients_of(&clients, COPY_TRANSLATION);
+                                }
                                 // everything else doesn't matter to me
                                 // This isn't a keylogger after all 0.o
                                 _ => {}
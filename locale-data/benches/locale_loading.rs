@@ -0,0 +1,106 @@
+// compares loading a locale from its json files with loading its precompiled bundle (time & heap)
+// cargo bench -p locale-data --bench locale_loading
+// measured on the shipped locales of the companion app
+
+use locale_data::{
+    bundle::{BUNDLE_FILE, build_bundle, load_bundle},
+    locale::{Locale, TextCategory},
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+const RUNS: u32 = 20;
+const LOCALE: &str = "DE";
+
+// counts the heap, only to compare how much memory both ways of loading need
+struct CountingAllocator;
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(allocated, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let locales_dir = std::env::temp_dir().join(format!(
+        "pokemmo_companion_locale_bench_{}",
+        std::process::id()
+    ));
+    let dir = locales_dir.join(LOCALE);
+    let _ = fs::remove_dir_all(&locales_dir);
+    fs::create_dir_all(&dir).unwrap();
+    for category in TextCategory::ALL {
+        let file = category.locale_file_name();
+        fs::copy(
+            manifest_dir
+                .join("../companion-app/assets/locales")
+                .join(LOCALE)
+                .join(file),
+            dir.join(file),
+        )
+        .unwrap();
+    }
+
+    // what "data-builder bundle" writes for a release
+    let bundle = build_bundle(&dir).unwrap();
+    fs::write(dir.join(BUNDLE_FILE), bincode::serialize(&bundle).unwrap()).unwrap();
+    assert!(load_bundle(&dir).unwrap().is_some());
+    println!(
+        "{LOCALE}: json files {} KiB, {BUNDLE_FILE} {} KiB",
+        TextCategory::ALL
+            .iter()
+            .map(|c| fs::metadata(dir.join(c.locale_file_name())).unwrap().len())
+            .sum::<u64>()
+            / 1024,
+        fs::metadata(dir.join(BUNDLE_FILE)).unwrap().len() / 1024
+    );
+
+    // the json path normalizes the search texts while loading, just like the bundle has them
+    measure("json", &|| {
+        Locale::parse_from_dir(&dir, "Deutsch".to_owned()).unwrap()
+    });
+    measure("bundle", &|| {
+        Locale::load_from_dir(&dir, "Deutsch".to_owned()).unwrap()
+    });
+
+    let _ = fs::remove_dir_all(&locales_dir);
+}
+
+fn measure(label: &str, load: &dyn Fn() -> Locale) {
+    load(); // warm up the file cache
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        load();
+    }
+    let duration = start.elapsed() / RUNS;
+
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let locale = load();
+    let peak = PEAK.load(Ordering::Relaxed) - before;
+    let retained = ALLOCATED.load(Ordering::Relaxed) - before;
+    println!(
+        "{label:<7} {duration:>10.2?} per locale, peak heap {:>6} KiB, retained {:>6} KiB ({} texts)",
+        peak / 1024,
+        retained / 1024,
+        locale.localized_texts.len()
+    );
+}
@@ -0,0 +1,170 @@
+// precompiled locale, built by "data-builder bundle" next to the json files of a locale (for
+// releases, the repo only has the json files). holds every text with its normalized search text
+// and loads a lot faster than the json files (benches/locale_loading.rs)
+// the json files stay the source of truth: a bundle is ignored once any of them has another size
+// than it was built from (locale updates also remove the bundle of the updated locale)
+
+use crate::{
+    locale::{LocalizedText, TextCategory},
+    normalize::normalize_prompt,
+};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{BufReader, ErrorKind},
+    path::Path,
+};
+
+pub const BUNDLE_FILE: &str = "locale.bundle";
+/// bumped whenever the layout or the search normalization changes
+pub const BUNDLE_FORMAT_VERSION: u32 = 2;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LocaleBundle {
+    pub format_version: u32,
+    pub files: Vec<BundleFile>,
+}
+
+/// the contents of one locale file, e.g. "monsters.json"
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleFile {
+    pub file: String,
+    pub size: u64, // of the json file the bundle was built from
+    pub entries: Vec<BundleEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleEntry {
+    pub key: String,
+    pub text: String,
+    pub search_text: String, // normalize_prompt(text)
+}
+
+/// the bundle of the json files in `locale_dir`, what "data-builder bundle" writes
+pub fn build_bundle(locale_dir: &Path) -> anyhow::Result<LocaleBundle> {
+    let mut files = Vec::new();
+    for category in TextCategory::ALL {
+        let path = locale_dir.join(category.locale_file_name());
+        let json = fs::read(&path).with_context(|| format!("couldn't read {path:?}"))?;
+        let texts: BTreeMap<String, String> =
+            serde_json::from_slice(&json).with_context(|| format!("couldn't parse {path:?}"))?;
+
+        files.push(BundleFile {
+            file: category.locale_file_name().to_owned(),
+            size: json.len() as u64,
+            entries: texts
+                .into_iter()
+                .map(|(key, text)| BundleEntry {
+                    search_text: normalize_prompt(&text),
+                    key,
+                    text,
+                })
+                .collect(),
+        });
+    }
+
+    Ok(LocaleBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        files,
+    })
+}
+
+/// the texts of the bundle in `locale_dir`, None if there is none
+/// errors when it's broken, outdated or doesn't match the json files anymore
+pub fn load_bundle(locale_dir: &Path) -> anyhow::Result<Option<Vec<LocalizedText>>> {
+    let file = match fs::File::open(locale_dir.join(BUNDLE_FILE)) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    // streamed, the raw bytes never have to be in memory next to the texts
+    let bundle: LocaleBundle = bincode::deserialize_from(BufReader::new(file))?;
+    if bundle.format_version != BUNDLE_FORMAT_VERSION {
+        anyhow::bail!(
+            "bundle format v{} isn't supported (v{BUNDLE_FORMAT_VERSION} is)",
+            bundle.format_version
+        );
+    }
+
+    let mut texts = Vec::with_capacity(bundle.files.iter().map(|f| f.entries.len()).sum());
+    for category in TextCategory::ALL {
+        let file_name = category.locale_file_name();
+        let Some(file) = bundle.files.iter().find(|f| f.file == file_name) else {
+            anyhow::bail!("{file_name} is missing in the bundle");
+        };
+        // e.g. a translator editing the json by hand
+        // only the size is checked: reading (or hashing) the json would cost what the bundle saves
+        // and timestamps don't survive copying the files into a release
+        let json = fs::metadata(locale_dir.join(file_name))?;
+        if json.len() != file.size {
+            anyhow::bail!("{file_name} changed since the bundle was built");
+        }
+    }
+
+    for file in bundle.files {
+        let Some(category) = TextCategory::ALL
+            .into_iter()
+            .find(|c| c.locale_file_name() == file.file)
+        else {
+            continue; // from a newer data-builder, which knows more categories
+        };
+        texts.extend(file.entries.into_iter().map(|entry| LocalizedText {
+            key: entry.key,
+            text: entry.text,
+            search_text: entry.search_text,
+            category,
+        }));
+    }
+    Ok(Some(texts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{locale::Locale, test_utils::temp_dir};
+
+    #[test]
+    fn test_bundle_matches_json() {
+        let dir = temp_dir("locale_bundle");
+        assert!(load_bundle(&dir).unwrap().is_none());
+
+        for category in TextCategory::ALL {
+            fs::write(dir.join(category.locale_file_name()), "{}").unwrap();
+        }
+        fs::write(
+            dir.join("monsters.json"),
+            r#"{"flabebe": "Flabébé", "mr_mime": "Pantimos  Straße"}"#,
+        )
+        .unwrap();
+        let bundle = build_bundle(&dir).unwrap();
+        assert_eq!(bundle.files.len(), TextCategory::ALL.len());
+        assert_eq!(
+            bundle.files[0].size,
+            fs::metadata(dir.join("monsters.json")).unwrap().len()
+        );
+        fs::write(dir.join(BUNDLE_FILE), bincode::serialize(&bundle).unwrap()).unwrap();
+
+        let texts = load_bundle(&dir).unwrap().unwrap();
+        let flabebe = texts.iter().find(|t| t.key == "flabebe").unwrap();
+        assert_eq!(flabebe.text, "Flabébé");
+        assert_eq!(flabebe.search_text, "flabebe");
+        assert_eq!(flabebe.category, TextCategory::Monster);
+        let mr_mime = texts.iter().find(|t| t.key == "mr_mime").unwrap();
+        assert_eq!(mr_mime.search_text, "pantimos strasse");
+        assert_eq!(texts.len(), 2);
+
+        // copied again (e.g. into a release), so newer than the bundle, but still the same file
+        fs::write(dir.join("skills.json"), "{}").unwrap();
+        assert!(load_bundle(&dir).unwrap().is_some());
+
+        // the json was changed afterwards -> the bundle is outdated
+        fs::write(dir.join("monsters.json"), r#"{"mr_mime": "Pantimos"}"#).unwrap();
+        assert!(load_bundle(&dir).is_err());
+        let locale = Locale::load_from_dir(&dir, "Deutsch".to_owned()).unwrap();
+        assert_eq!(locale.localized_texts.len(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,111 @@
+// stable ids of a locale's texts, written by "data-builder" next to the json files
+// names are only unique within one category ("metronome" is a move and an item), ids are unique
+// per category and don't change when a name is corrected, so callers knowing one should use it
+
+use crate::locale::TextCategory;
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+pub const IDS_FILE: &str = "ids.json";
+
+/// category -> id -> key into that category's locale file
+/// monsters use their dex id, moves/items their pokedex dump id, misc texts the id of the game's string dump
+pub type LocaleIds = BTreeMap<TextCategory, BTreeMap<u32, String>>;
+
+/// empty if the locale has no ids (yet), e.g. locales built by an older data-builder
+pub fn load_ids(locale_dir: &Path) -> io::Result<LocaleIds> {
+    let content = match fs::read_to_string(locale_dir.join(IDS_FILE)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(LocaleIds::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{locale::Locale, test_utils::temp_dir};
+    use std::path::Path;
+
+    #[test]
+    fn test_resolve_by_id() {
+        let dir = temp_dir("locale_ids");
+        for category in TextCategory::ALL {
+            fs::write(dir.join(category.locale_file_name()), "{}").unwrap();
+        }
+        fs::write(dir.join("skills.json"), r#"{"metronome": "Metronom"}"#).unwrap();
+        fs::write(
+            dir.join("item_names.json"),
+            r#"{"metronome": "Metronom-Item"}"#,
+        )
+        .unwrap();
+
+        // without ids only the names work
+        let locale = Locale::load_from_dir(&dir, "Deutsch".to_owned()).unwrap();
+        assert_eq!(locale.get_text_by_id(TextCategory::Item, 277), None);
+        assert_eq!(
+            locale.get_text(TextCategory::Item, "metronome"),
+            Some("Metronom-Item")
+        );
+        // the compatibility lookup can only guess the category
+        assert_eq!(locale.get_localized_text("metronome"), Some("Metronom"));
+
+        fs::write(
+            dir.join(IDS_FILE),
+            r#"{"Move": {"118": "metronome"}, "Item": {"277": "metronome", "1": "unknown"}}"#,
+        )
+        .unwrap();
+        let locale = Locale::load_from_dir(&dir, "Deutsch".to_owned()).unwrap();
+        assert_eq!(
+            locale.get_text_by_id(TextCategory::Move, 118),
+            Some("Metronom")
+        );
+        assert_eq!(
+            locale.get_text_by_id(TextCategory::Item, 277),
+            Some("Metronom-Item")
+        );
+        assert_eq!(locale.get_text_by_id(TextCategory::Item, 118), None);
+        assert_eq!(locale.get_text_by_id(TextCategory::Item, 1), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_shipped_locales_resolve_by_id() {
+        let locales_dir =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../companion-app/assets/locales");
+        for locale in ["EN", "DE", "FR", "ES", "IT"] {
+            let dir = locales_dir.join(locale);
+            let ids = load_ids(&dir).unwrap();
+            // every monster of the dex (1 - 649) has its id
+            assert_eq!(
+                ids.get(&TextCategory::Monster).map(|ids| ids.len()),
+                Some(649),
+                "{locale}"
+            );
+            let locale = Locale::load_from_dir(&dir, locale.to_owned()).unwrap();
+            for (category, category_ids) in &ids {
+                for (id, key) in category_ids {
+                    assert_eq!(
+                        locale.get_text_by_id(*category, *id),
+                        locale.get_text(*category, key)
+                    );
+                }
+            }
+        }
+
+        let locale = Locale::load_from_dir(locales_dir.join("DE"), "Deutsch".to_owned()).unwrap();
+        assert_eq!(
+            locale.get_text_by_id(TextCategory::Monster, 445),
+            Some("Knakrack")
+        );
+        assert_eq!(
+            locale.get_text_by_id(TextCategory::Move, 118),
+            Some("Metronom")
+        );
+        assert_eq!(
+            locale.get_text_by_id(TextCategory::Item, 5277),
+            Some("Metronom")
+        );
+    }
+}
@@ -0,0 +1,9 @@
+// what the companion app and the data-builder both need to know about locales, so neither has to
+// keep a copy of the other's code in sync
+pub mod bundle;
+pub mod ids;
+pub mod locale;
+pub mod normalize;
+#[cfg(test)]
+mod test_utils;
+pub mod text_coverage;
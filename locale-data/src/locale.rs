@@ -0,0 +1,301 @@
+// the texts of one locale, loaded from its json files (one per category) or its precompiled bundle
+
+use crate::{
+    bundle,
+    ids::{self, LocaleIds},
+    normalize::normalize_prompt,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::Path,
+    sync::Arc,
+};
+
+/// the type of list a localized text is from (and what category of thing it is)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum TextCategory {
+    Monster,
+    Move,
+    Location,
+    PokedexLocation, // contains time-information inseparable from main name
+    Item,
+    ItemDescription,
+    Miscellaneous,
+}
+
+impl TextCategory {
+    pub const ALL: [TextCategory; 7] = [
+        TextCategory::Monster,
+        TextCategory::Move,
+        TextCategory::Location,
+        TextCategory::PokedexLocation,
+        TextCategory::Item,
+        TextCategory::ItemDescription,
+        TextCategory::Miscellaneous,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TextCategory::Monster => "Pokémon",
+            TextCategory::Move => "Moves",
+            TextCategory::Location => "Locations",
+            TextCategory::PokedexLocation => "Pokédex Locations",
+            TextCategory::Item => "Items",
+            TextCategory::ItemDescription => "Item Descriptions",
+            TextCategory::Miscellaneous => "Misc",
+        }
+    }
+
+    /// the file of a locale the category is stored in
+    pub fn locale_file_name(&self) -> &'static str {
+        match self {
+            TextCategory::Monster => "monsters.json",
+            TextCategory::Move => "skills.json",
+            TextCategory::Location => "locations.json",
+            TextCategory::PokedexLocation => "locations_pokedex.json",
+            TextCategory::Item => "item_names.json",
+            TextCategory::ItemDescription => "item_descriptions.json",
+            TextCategory::Miscellaneous => "miscellaneous.json",
+        }
+    }
+
+    /// descriptions are whole sentences, everything else is a name
+    pub fn is_long_text(&self) -> bool {
+        matches!(
+            self,
+            TextCategory::ItemDescription | TextCategory::Miscellaneous
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct LocalizedText {
+    pub key: String,
+    pub text: String,
+    pub search_text: String, // normalize_prompt(text), so searching doesn't have to
+    pub category: TextCategory,
+}
+
+impl LocalizedText {
+    pub fn new(category: TextCategory, key: String, text: String) -> Self {
+        Self {
+            search_text: normalize_prompt(&text),
+            key,
+            text,
+            category,
+        }
+    }
+}
+
+/// contains all the key-translation pairs for each catagory
+#[derive(Debug)]
+pub struct Locale {
+    #[allow(dead_code)]
+    pub locale_name: String,
+    pub localized_texts: Vec<Arc<LocalizedText>>,
+    pub monsters: HashMap<String, usize>,
+    pub moves: HashMap<String, usize>,
+    pub locations: HashMap<String, usize>,
+    pub locations_pokedex: HashMap<String, usize>,
+    pub items: HashMap<String, usize>,
+    pub item_descriptions: HashMap<String, usize>,
+    pub miscellaneous: HashMap<String, usize>,
+    pub ids: HashMap<(TextCategory, u32), usize>, // from the locale's ids.json, if it has one
+}
+
+impl Locale {
+    /// the text with the stable id of the data-builder (e.g. the pokedex id of a monster)
+    /// None if there is no (non-empty) text for it or the locale has no ids
+    pub fn get_text_by_id(&self, category: TextCategory, id: u32) -> Option<&str> {
+        self.text_at(*self.ids.get(&(category, id))?)
+    }
+
+    /// the text of `key` in exactly this category, None if there is no (non-empty) text for it
+    pub fn get_text(&self, category: TextCategory, key: &str) -> Option<&str> {
+        self.text_at(*self.category_indices(category).get(key)?)
+    }
+
+    /// like get_text, but returns the key back when it doesn't find anything
+    pub fn find_text(&self, category: TextCategory, key: &str) -> String {
+        match self.get_text(category, key) {
+            Some(text) => text.to_owned(),
+            None => key.into(),
+        }
+    }
+
+    /// only for callers that don't know the category of a name (e.g. the data keys of guides)
+    /// looks through any dictionary in order of importance and returns key back when it doesn't find anything
+    pub fn find_localized_text(&self, key: &str) -> String {
+        match self.get_localized_text(key) {
+            Some(text) => text.to_owned(),
+            None => key.into(),
+        }
+    }
+
+    /// like find_localized_text, but None if there is no (non-empty) text for the key
+    /// a key of multiple categories resolves to the first one, prefer get_text/get_text_by_id
+    pub fn get_localized_text(&self, key: &str) -> Option<&str> {
+        TextCategory::ALL
+            .into_iter()
+            .find_map(|category| self.get_text(category, key))
+    }
+
+    fn text_at(&self, index: usize) -> Option<&str> {
+        self.localized_texts
+            .get(index)
+            .map(|loc_text| loc_text.text.as_str())
+            .filter(|text| !text.is_empty())
+    }
+
+    /// key -> text of one category (i.e. the contents of its locale file)
+    pub fn get_category_texts(&self, category: TextCategory) -> HashMap<&str, &str> {
+        self.category_indices(category)
+            .iter()
+            .filter_map(|(key, index)| {
+                let text = self.localized_texts.get(*index)?;
+                Some((key.as_str(), text.text.as_str()))
+            })
+            .collect()
+    }
+
+    fn category_indices(&self, category: TextCategory) -> &HashMap<String, usize> {
+        match category {
+            TextCategory::Monster => &self.monsters,
+            TextCategory::Move => &self.moves,
+            TextCategory::Location => &self.locations,
+            TextCategory::PokedexLocation => &self.locations_pokedex,
+            TextCategory::Item => &self.items,
+            TextCategory::ItemDescription => &self.item_descriptions,
+            TextCategory::Miscellaneous => &self.miscellaneous,
+        }
+    }
+
+    /// replaces the text of `key` (or adds it, if the shipped locale doesn't have it)
+    pub fn set_text(&mut self, category: TextCategory, key: &str, text: &str) {
+        self.insert_text(LocalizedText::new(
+            category,
+            key.to_owned(),
+            text.to_owned(),
+        ));
+    }
+
+    fn insert_text(&mut self, localized_text: LocalizedText) {
+        let indices = match localized_text.category {
+            TextCategory::Monster => &mut self.monsters,
+            TextCategory::Move => &mut self.moves,
+            TextCategory::Location => &mut self.locations,
+            TextCategory::PokedexLocation => &mut self.locations_pokedex,
+            TextCategory::Item => &mut self.items,
+            TextCategory::ItemDescription => &mut self.item_descriptions,
+            TextCategory::Miscellaneous => &mut self.miscellaneous,
+        };
+
+        match indices.get(&localized_text.key) {
+            Some(index) => self.localized_texts[*index] = Arc::new(localized_text),
+            None => {
+                indices.insert(localized_text.key.clone(), self.localized_texts.len());
+                self.localized_texts.push(Arc::new(localized_text));
+            }
+        }
+    }
+
+    /// ids pointing to keys this locale doesn't have are skipped
+    pub fn set_ids(&mut self, ids: &LocaleIds) {
+        self.ids.clear();
+        for (category, category_ids) in ids {
+            for (id, key) in category_ids {
+                if let Some(index) = self.category_indices(*category).get(key) {
+                    self.ids.insert((*category, *id), *index);
+                }
+            }
+        }
+    }
+
+    /// category -> key -> text, e.g. the user fixes of the app
+    pub fn apply_overrides(
+        &mut self,
+        overrides: &BTreeMap<TextCategory, BTreeMap<String, String>>,
+    ) {
+        for (category, texts) in overrides {
+            for (key, text) in texts {
+                self.set_text(*category, key, text);
+            }
+        }
+    }
+
+    fn from_texts(locale_name: String, texts: Vec<LocalizedText>) -> Self {
+        // sized up front, growing the maps would leave up to half of them unused
+        let count = |category| texts.iter().filter(|t| t.category == category).count();
+        let mut locale = Self {
+            locale_name,
+            localized_texts: Vec::with_capacity(texts.len()),
+            monsters: HashMap::with_capacity(count(TextCategory::Monster)),
+            moves: HashMap::with_capacity(count(TextCategory::Move)),
+            locations: HashMap::with_capacity(count(TextCategory::Location)),
+            locations_pokedex: HashMap::with_capacity(count(TextCategory::PokedexLocation)),
+            items: HashMap::with_capacity(count(TextCategory::Item)),
+            item_descriptions: HashMap::with_capacity(count(TextCategory::ItemDescription)),
+            miscellaneous: HashMap::with_capacity(count(TextCategory::Miscellaneous)),
+            ids: HashMap::new(),
+        };
+        for text in texts {
+            locale.insert_text(text);
+        }
+        locale
+    }
+
+    /// uses the precompiled bundle of the locale if there is a valid one, the json files otherwise
+    /// the ids are optional, without them only the names resolve
+    pub fn load_from_dir(dir: impl AsRef<Path>, locale_name: String) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        let mut locale = match bundle::load_bundle(dir) {
+            Ok(Some(texts)) => Self::from_texts(locale_name, texts),
+            Ok(None) => Self::parse_from_dir(dir, locale_name)?,
+            Err(e) => {
+                eprintln!(
+                    "LocaleSubsystem - ignoring the bundle of {locale_name}, because {e} (loading json instead)"
+                );
+                Self::parse_from_dir(dir, locale_name)?
+            }
+        };
+
+        match ids::load_ids(dir) {
+            Ok(ids) => locale.set_ids(&ids),
+            Err(e) => eprintln!(
+                "LocaleSubsystem - ignoring the ids of {}, because {e}",
+                locale.locale_name
+            ),
+        }
+        Ok(locale)
+    }
+
+    /// errors when the parse function for any file fails
+    pub fn parse_from_dir(dir: impl AsRef<Path>, locale_name: String) -> io::Result<Self> {
+        let dir_path = dir.as_ref();
+
+        // one flat localized_texts list + a key -> index map per category, in category order
+        let mut texts = Vec::new();
+        for category in TextCategory::ALL {
+            let file_texts: HashMap<String, String> =
+                parse_json_file(dir_path.join(category.locale_file_name()))?;
+            texts.extend(
+                file_texts
+                    .into_iter()
+                    .map(|(key, text)| LocalizedText::new(category, key, text)),
+            );
+        }
+
+        Ok(Self::from_texts(locale_name, texts))
+    }
+}
+
+fn parse_json_file<T>(path: impl AsRef<Path>) -> io::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let content = fs::read_to_string(path)?;
+    let value = serde_json::from_str(&content)?;
+    Ok(value)
+}
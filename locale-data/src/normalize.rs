@@ -0,0 +1,29 @@
+// search normalization, the app searches with it and the data-builder writes the search texts of
+// the locale bundles with it
+
+/// using this makes it easier to search for certain things in unfirmiliar languages or the damn Pokè
+fn normalize_char(c: char) -> String {
+    match c.to_ascii_lowercase() {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a".to_string(),
+        'è' | 'é' | 'ê' | 'ë' => "e".to_string(),
+        'ì' | 'í' | 'î' | 'ï' => "i".to_string(),
+        'ò' | 'ó' | 'ô' | 'ö' | 'õ' => "o".to_string(),
+        'ù' | 'ú' | 'û' | 'ü' => "u".to_string(),
+        'ñ' => "n".to_string(),
+        'ç' => "c".to_string(),
+        'ß' => "ss".to_string(),
+        _ => c.to_ascii_lowercase().to_string(),
+    }
+}
+
+fn normalize_string(s: &str) -> String {
+    s.chars().map(normalize_char).collect()
+}
+
+/// normalized words, separated by exactly one space
+pub fn normalize_prompt(s: &str) -> String {
+    normalize_string(s)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,11 @@
+// helpers shared by the tests
+
+use std::{fs, path::PathBuf};
+
+/// empty directory in the system's temp dir, unique per test `name` and test run
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("locale_data_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
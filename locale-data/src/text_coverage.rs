@@ -0,0 +1,89 @@
+// compares the texts of one locale to the base locale (english), flags everything a translator
+// should look at. used by the app's locale coverage report and the data-builder's "coverage"
+
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub struct TextCoverage {
+    pub base_count: usize,
+    pub missing: Vec<String>, // in the base data, but not in this locale
+    pub empty: Vec<String>,   // falls back to the key in the app, just like missing ones
+    pub same_as_base: Vec<String>, // identical to english, might not be translated yet
+    pub unknown: Vec<String>, // not in the base data at all
+}
+
+impl TextCoverage {
+    pub fn translated_count(&self) -> usize {
+        self.base_count
+            .saturating_sub(self.missing.len() + self.empty.len())
+    }
+
+    pub fn issue_count(&self) -> usize {
+        self.missing.len() + self.empty.len() + self.same_as_base.len() + self.unknown.len()
+    }
+}
+
+/// key -> text maps of the base locale and the checked one, all key lists are sorted
+pub fn compare_texts(
+    base_texts: &HashMap<&str, &str>,
+    texts: &HashMap<&str, &str>,
+    is_base: bool, // the base is always "identical to itself"
+) -> TextCoverage {
+    let mut coverage = TextCoverage {
+        base_count: base_texts.len(),
+        ..Default::default()
+    };
+
+    for (key, base_text) in base_texts {
+        match texts.get(key) {
+            None => coverage.missing.push(key.to_string()),
+            Some(text) if text.trim().is_empty() => coverage.empty.push(key.to_string()),
+            Some(text) if !is_base && text == base_text => {
+                coverage.same_as_base.push(key.to_string())
+            }
+            Some(_) => (),
+        }
+    }
+    coverage.unknown = texts
+        .keys()
+        .filter(|key| !base_texts.contains_key(*key))
+        .map(|key| key.to_string())
+        .collect();
+
+    coverage.missing.sort();
+    coverage.empty.sort();
+    coverage.same_as_base.sort();
+    coverage.unknown.sort();
+    coverage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_texts() {
+        let base = HashMap::from([
+            ("garchomp", "Garchomp"),
+            ("pikachu", "Pikachu"),
+            ("leftovers", "Leftovers"),
+            ("timid", "Timid"),
+        ]);
+        let german = HashMap::from([
+            ("garchomp", "Knakrack"),
+            ("pikachu", "Pikachu"),
+            ("leftovers", " "),
+            ("lefotvers", "Überreste"),
+        ]);
+
+        let coverage = compare_texts(&base, &german, false);
+        assert_eq!(coverage.missing, vec!["timid"]);
+        assert_eq!(coverage.empty, vec!["leftovers"]);
+        assert_eq!(coverage.same_as_base, vec!["pikachu"]);
+        assert_eq!(coverage.unknown, vec!["lefotvers"]);
+        assert_eq!(coverage.translated_count(), 2);
+
+        let base_coverage = compare_texts(&base, &base, true);
+        assert_eq!(base_coverage.issue_count(), 0);
+    }
+}